@@ -0,0 +1,103 @@
+//! Automatic selection of the most readable unit for a value.
+
+use num::Zero;
+
+use crate::compound::Compound;
+use crate::numeric::Numeric;
+use crate::powers::Powers;
+use crate::prefix::Prefix;
+use crate::unit::{Derived, Unit};
+use crate::units;
+
+/// Named derived units which are preferred over the base units they are made
+/// up of.
+///
+/// Units which share dimensions with more common ones, like the becquerel or
+/// the gray, are left out so that they aren't picked by accident.
+static NAMED: [&Derived; 13] = [
+    &units::NEWTON,
+    &units::PASCAL,
+    &units::energy::JOULE,
+    &units::WATT,
+    &units::COULOMB,
+    &units::VOLT,
+    &units::FARAD,
+    &units::OHM,
+    &units::SIEMENS,
+    &units::WEBER,
+    &units::TESLA,
+    &units::HENRY,
+    &units::LUX,
+];
+
+/// Pick the most readable unit for the given value.
+///
+/// This is a named derived unit if the dimensions of the value match one, and
+/// an SI prefix which keeps the value between 1 and 1000. Returns `None` if
+/// the unit of the value should be kept as it is.
+///
+/// Values in a single unit which can't be prefixed, like `btu` or `hr`, are
+/// kept unless `explicit` is set, in which case they are converted into a
+/// matching named derived unit if there is one.
+pub(crate) fn best(value: &Numeric, explicit: bool) -> Option<Compound> {
+    if value.mixed.is_some() || value.is_instant() {
+        return None;
+    }
+
+    let unit = match value.unit.single() {
+        Some((unit, state)) if state.power == 1 && (!explicit || max_prefix(&unit).is_some()) => {
+            // NB: values which are already readable keep the unit they were
+            // given in, like `5 cm`.
+            if value.value.is_zero() || (0..3).contains(&value.value.magnitude()) {
+                return None;
+            }
+
+            unit
+        }
+        _ => {
+            let (_, powers) = value.unit.base_units();
+            Unit::Derived(*NAMED.iter().copied().find(|d| same(&powers, d))?)
+        }
+    };
+
+    let max = max_prefix(&unit)?;
+
+    let target = Compound::from_iter([(unit.clone(), (1, 0))]);
+    let mut scaled = value.value.clone();
+
+    if !matches!(target.factor(&value.unit, &mut scaled), Ok(true)) {
+        return None;
+    }
+
+    if scaled.is_zero() {
+        return Some(target);
+    }
+
+    let bias = unit.prefix_bias();
+    let exponent = (scaled.magnitude() + bias).div_euclid(3) * 3;
+    let exponent = exponent.clamp(Prefix::YOCTO, max);
+    let (_, extra) = Prefix::find(exponent);
+    let prefix = exponent + extra;
+
+    Some(Compound::from_iter([(unit, (1, prefix - bias))]))
+}
+
+/// Test if the given derived unit has the given dimensions.
+fn same(powers: &Powers, derived: &Derived) -> bool {
+    let mut other = Powers::default();
+    (derived.vtable.powers)(&mut other, 1);
+    powers.iter().eq(other.iter())
+}
+
+/// Get the largest prefix which is used for the given unit, or `None` if the
+/// unit shouldn't be prefixed.
+fn max_prefix(unit: &Unit) -> Option<i32> {
+    match unit {
+        // NB: kiloseconds and megagrams are correct but not commonly used.
+        Unit::Second => Some(Prefix::NONE),
+        Unit::KiloGram => Some(Prefix::KILO),
+        Unit::Meter | Unit::Ampere | Unit::Mole | Unit::Candela => Some(Prefix::YOTTA),
+        Unit::Derived(derived) if NAMED.contains(&derived) => Some(Prefix::YOTTA),
+        _ => None,
+    }
+}
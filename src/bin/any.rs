@@ -1,12 +1,100 @@
-use anything::rational::DisplaySpec;
+use anyhow::{anyhow, Context as _};
+use anything::rational::{DisplayMode, DisplaySpec};
+use anything::{
+    Compound, Context, Db, Description, Facts, Import, Numeric, Rates, Rational, Source,
+};
 use codespan_reporting::diagnostic::{Diagnostic, Label};
 use codespan_reporting::files::{Files, SimpleFiles};
 use codespan_reporting::term;
 use codespan_reporting::term::termcolor::{ColorChoice, StandardStream};
-use num::One;
-use std::io::Write;
+use num::{One, Zero};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
 use structopt::StructOpt;
 
+/// The name of the file interactive history is stored in.
+const HISTORY: &str = "history.txt";
+/// The name of the file custom units are loaded from in the config directory.
+const UNITS: &str = "units.toml";
+/// The name of the file exchange rates are loaded from in the config directory.
+const RATES: &str = "rates.toml";
+/// The name of the directory user facts are loaded from in the config
+/// directory.
+const FACTS: &str = "facts";
+
+/// The format results are printed in.
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Text,
+    Json,
+    Ndjson,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "ndjson" => Ok(Format::Ndjson),
+            _ => Err(anyhow!(
+                "unsupported format `{s}`, expected text, json or ndjson"
+            )),
+        }
+    }
+}
+
+// Commands which browse the fact database instead of running a query.
+//
+// NB: this isn't a doc comment, since structopt would use it as the about
+// text of `any` itself.
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Search the fact database for constants matching the given terms.
+    Search {
+        /// The maximum number of constants to list.
+        #[structopt(long, default_value = "10")]
+        limit: usize,
+        /// The terms to search for.
+        #[structopt(required = true)]
+        terms: Vec<String>,
+    },
+    /// List the sources of the fact database, or every constant from one
+    /// source.
+    List {
+        /// The identifier of the source to list constants from.
+        #[structopt(long)]
+        source: Option<u64>,
+    },
+    /// Manage the user fact database.
+    Db(DbCommand),
+}
+
+/// Commands which manage the user fact database.
+#[derive(Debug, StructOpt)]
+enum DbCommand {
+    /// Import facts from a CSV, JSON or XLSX file as described by a mapping
+    /// file, and store them in the `facts` directory in the configuration
+    /// directory.
+    Import {
+        /// Write the facts to the given TOML file instead.
+        #[structopt(long, parse(from_os_str))]
+        output: Option<PathBuf>,
+        /// The mapping file describing what to import.
+        #[structopt(parse(from_os_str))]
+        mapping: PathBuf,
+    },
+}
+
 #[derive(Debug, StructOpt)]
 #[structopt(
     name = "any",
@@ -16,32 +104,429 @@ struct Opts {
     /// Describe the looked up components in the expression.
     #[structopt(long)]
     describe: bool,
-    /// Show the exact fractional result.
+    /// Show the candidates which were considered when looking up constants,
+    /// along with their scores.
+    #[structopt(long)]
+    candidates: bool,
+    /// Keep results in the unit they were calculated in, instead of picking
+    /// the most readable unit when there is no `to` cast.
+    #[structopt(long)]
+    no_auto: bool,
+    /// Show the exact fractional result. Approximate results are refused.
     #[structopt(long)]
     exact: bool,
     /// Dump syntax tree.
     #[structopt(long)]
     syntax: bool,
-    /// The query to run.
+    /// Start an interactive session. This is the default if no query is
+    /// provided.
+    #[structopt(long)]
+    repl: bool,
+    /// Load custom units from the given TOML file. Units are also loaded from
+    /// `units.toml` in the configuration directory if it exists.
+    #[structopt(long, number_of_values = 1, parse(from_os_str))]
+    units: Vec<PathBuf>,
+    /// Load facts from the given TOML file, in the same `[[constants]]` format
+    /// as the database is built from. Facts are also loaded from every
+    /// `.toml` file in the `facts` directory in the configuration directory.
+    #[structopt(long, number_of_values = 1, parse(from_os_str))]
+    facts: Vec<PathBuf>,
+    /// Load exchange rates from the given TOML file, overriding the rates
+    /// shipped with the database. Rates are also loaded from `rates.toml` in
+    /// the configuration directory if it exists.
+    #[structopt(long, number_of_values = 1, parse(from_os_str))]
+    rates: Vec<PathBuf>,
+    /// Use the most recent exchange rates on or before the given date,
+    /// formatted as `YYYY-MM-DD`.
+    #[structopt(long)]
+    as_of: Option<String>,
+    /// The format to print results in: `text`, `json` with a single document
+    /// per query, or `ndjson` with one document per result.
+    #[structopt(long, default_value = "text")]
+    format: Format,
+    /// Evaluate each line of the given file, or standard input if it is `-`.
+    /// Empty lines and lines starting with `#` are skipped.
+    #[structopt(short = "f", long = "file", parse(from_os_str))]
+    file: Option<PathBuf>,
+    /// Round results to the given number of significant figures.
+    #[structopt(long, conflicts_with_all = &["decimals", "scientific", "engineering"])]
+    sig_figs: Option<usize>,
+    /// Round results to the given number of decimal places.
+    #[structopt(long, conflicts_with_all = &["scientific", "engineering"])]
+    decimals: Option<usize>,
+    /// Show results in scientific notation with the given number of
+    /// significant figures.
+    #[structopt(long, conflicts_with = "engineering")]
+    scientific: Option<usize>,
+    /// Show results in engineering notation, where the exponent is a multiple
+    /// of three, with the given number of significant figures.
+    #[structopt(long)]
+    engineering: Option<usize>,
+    /// The query to run, or `-` to evaluate each line of standard input.
     query: Vec<String>,
+    #[structopt(subcommand)]
+    command: Option<Command>,
 }
 
-fn main() -> anyhow::Result<()> {
+fn main() -> anyhow::Result<ExitCode> {
     let opts = Opts::from_args();
 
     pretty_env_logger::init();
 
     let mut out = StandardStream::stdout(ColorChoice::Auto);
 
-    let query = opts.query.join(" ");
+    if let Some(Command::Db(command)) = &opts.command {
+        manage(&mut out, command)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut facts = Facts::new();
+    let default_facts = anything::config_dir()?.join(FACTS);
+
+    if default_facts.is_dir() {
+        let mut paths = fs::read_dir(&default_facts)?
+            .map(|e| Ok(e?.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        paths.sort();
+
+        for path in paths {
+            if path.extension().is_some_and(|e| e == "toml") {
+                facts.load_path(&path)?;
+            }
+        }
+    }
+
+    for path in &opts.facts {
+        facts.load_path(path)?;
+    }
+
+    let db = Db::open_with(&facts)?;
 
-    let db = anything::Db::open()?;
+    if let Some(command) = &opts.command {
+        browse(&mut out, &opts, &db, command)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mut ctx = Context::new();
+
+    let default_units = anything::config_dir()?.join(UNITS);
+
+    if default_units.is_file() {
+        load_units(&mut ctx, &default_units)?;
+    }
+
+    for path in &opts.units {
+        load_units(&mut ctx, path)?;
+    }
+
+    let mut rates = Rates::embedded()?;
+    let default_rates = anything::config_dir()?.join(RATES);
+
+    if default_rates.is_file() {
+        load_rates(&mut rates, &default_rates)?;
+    }
+
+    for path in &opts.rates {
+        load_rates(&mut rates, path)?;
+    }
+
+    match rates.as_of(opts.as_of.as_deref()) {
+        Ok(day) => ctx.define_currencies(day)?,
+        Err(error) if opts.as_of.is_some() => return Err(error.context("--as-of")),
+        Err(error) => log::warn!("currencies not available: {error}"),
+    }
+
+    let success = if let Some(path) = &opts.file {
+        batch(&mut out, &opts, &db, &mut ctx, path)?
+    } else if opts.query == ["-"] {
+        batch(&mut out, &opts, &db, &mut ctx, Path::new("-"))?
+    } else if opts.repl || opts.query.is_empty() {
+        repl(&mut out, &opts, &db, &mut ctx)?;
+        true
+    } else {
+        let query = opts.query.join(" ");
+        let len = query.len();
+
+        let mut files = SimpleFiles::new();
+        let id = files.add("<in>".to_owned(), query);
+        run(&mut out, &opts, &db, &mut ctx, &files, id, 0..len)?
+    };
+
+    Ok(if success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+/// Manage the user fact database.
+fn manage(out: &mut StandardStream, command: &DbCommand) -> anyhow::Result<()> {
+    match command {
+        DbCommand::Import { output, mapping } => {
+            let import = Import::open(mapping)?;
+
+            let output = match output {
+                Some(output) => output.clone(),
+                None => {
+                    let Some(stem) = mapping.file_stem() else {
+                        return Err(anyhow!("{}: not a file", mapping.display()));
+                    };
+
+                    let dir = anything::config_dir()?.join(FACTS);
+                    fs::create_dir_all(&dir)?;
+                    dir.join(stem).with_extension("toml")
+                }
+            };
+
+            // NB: don't clobber the mapping if it lives in the facts
+            // directory.
+            if output.exists() && fs::canonicalize(&output)? == fs::canonicalize(mapping)? {
+                return Err(anyhow!(
+                    "{}: refusing to overwrite the mapping file",
+                    output.display()
+                ));
+            }
+
+            fs::write(&output, import.to_toml()?)
+                .with_context(|| anyhow!("{}", output.display()))?;
+
+            writeln!(
+                out,
+                "Imported {} facts from {} into {}",
+                import.len(),
+                mapping.display(),
+                output.display()
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Browse the fact database.
+fn browse(out: &mut StandardStream, opts: &Opts, db: &Db, command: &Command) -> anyhow::Result<()> {
+    let constants = match command {
+        Command::Search { limit, terms } => db
+            .search(&terms.join(" "), *limit)?
+            .into_iter()
+            .map(|c| (Some(c.score), c.constant))
+            .collect::<Vec<_>>(),
+        Command::List {
+            source: Some(source),
+        } => {
+            if db.get_source(*source).is_none() {
+                return Err(anyhow!("no source with the identifier {source}"));
+            }
+
+            db.list(*source)?.into_iter().map(|c| (None, c)).collect()
+        }
+        Command::List { source: None } => {
+            for source in db.sources() {
+                match opts.format {
+                    Format::Text => {
+                        write!(out, "{}: {}", source.id, source.description)?;
+
+                        if let Some(url) = &source.url {
+                            write!(out, " <{url}>")?;
+                        }
+
+                        writeln!(out)?;
+                    }
+                    Format::Json | Format::Ndjson => {
+                        serde_json::to_writer(&mut *out, source)?;
+                        writeln!(out)?;
+                    }
+                }
+            }
+
+            return Ok(());
+        }
+        Command::Db(..) => unreachable!("managed before the database is opened"),
+    };
+
+    let spec = display_spec(opts);
+
+    for (score, c) in &constants {
+        let value = c.numeric();
+
+        match opts.format {
+            Format::Text => {
+                write!(out, "{}", c.description)?;
+
+                if let Some(score) = score {
+                    write!(out, " (score {score:.3})")?;
+                }
+
+                writeln!(out)?;
+                writeln!(out, "  tokens: {}", c.tokens.join(", "))?;
+
+                if let (Some(entity), Some(attribute)) = (&c.entity, &c.attribute) {
+                    writeln!(out, "  attribute: {attribute} of {entity}")?;
+                }
+
+                write!(out, "  value: ")?;
+
+                if !value.exact {
+                    write!(out, "≈ ")?;
+                }
+
+                match value.display_uncertainty(&spec) {
+                    Some(uncertain) if !opts.exact && spec.mode == DisplayMode::Auto => {
+                        write!(out, "{uncertain}")?
+                    }
+                    _ => write_rational(out, opts, &value.value)?,
+                }
+
+                write_unit(out, &value.value, &value.unit)?;
+                writeln!(out)?;
+
+                if let Some(source) = c.source.and_then(|id| db.get_source(id)) {
+                    write!(out, "  source: {}", source.id)?;
+                    write_source(out, Some(source))?;
+                    writeln!(out)?;
+                }
+            }
+            Format::Json | Format::Ndjson => {
+                let value = Ok(value);
+
+                let output = OutputConstant {
+                    score: *score,
+                    tokens: &c.tokens,
+                    entity: c.entity.as_deref(),
+                    attribute: c.attribute.as_deref(),
+                    description: &c.description,
+                    value: output_result(&value, &spec, 0),
+                    source: c.source.and_then(|id| db.get_source(id)),
+                };
+
+                serde_json::to_writer(&mut *out, &output)?;
+                writeln!(out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Evaluate each line of the given file, or standard input if the path is
+/// `-`, continuing past lines which fail.
+///
+/// Returns `false` if any line failed.
+fn batch(
+    out: &mut StandardStream,
+    opts: &Opts,
+    db: &Db,
+    ctx: &mut Context,
+    path: &Path,
+) -> anyhow::Result<bool> {
+    let (name, source) = if path == Path::new("-") {
+        let mut source = String::new();
+        io::stdin().read_to_string(&mut source)?;
+        ("<stdin>".to_owned(), source)
+    } else {
+        let source = fs::read_to_string(path).with_context(|| anyhow!("{}", path.display()))?;
+        (path.display().to_string(), source)
+    };
 
     let mut files = SimpleFiles::new();
-    let id = files.add("<in>", query);
+    let id = files.add(name, source);
 
-    let config = codespan_reporting::term::Config::default();
+    let mut success = true;
+    let mut start = 0;
+
+    for line in files.source(id)?.split_inclusive('\n') {
+        let indent = line.len() - line.trim_start().len();
+        let range = start + indent..start + line.trim_end().len();
+        start += line.len();
+
+        if range.is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
 
+        success &= run(out, opts, db, ctx, &files, id, range)?;
+    }
+
+    Ok(success)
+}
+
+/// Load custom units from the given path.
+fn load_units(ctx: &mut Context, path: &Path) -> anyhow::Result<()> {
+    let source = fs::read_to_string(path).with_context(|| anyhow!("{}", path.display()))?;
+
+    ctx.units_mut()
+        .load_toml(&source)
+        .with_context(|| anyhow!("{}", path.display()))?;
+
+    Ok(())
+}
+
+/// Load exchange rates from the given path.
+fn load_rates(rates: &mut Rates, path: &Path) -> anyhow::Result<()> {
+    let source = fs::read_to_string(path).with_context(|| anyhow!("{}", path.display()))?;
+
+    rates
+        .load_toml(&source)
+        .with_context(|| anyhow!("{}", path.display()))?;
+
+    Ok(())
+}
+
+/// Run an interactive session, where variables and previous results are kept
+/// between lines.
+fn repl(out: &mut StandardStream, opts: &Opts, db: &Db, ctx: &mut Context) -> anyhow::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+
+    let data_dir = anything::data_dir()?;
+    let history = data_dir.join(HISTORY);
+
+    if history.is_file() {
+        if let Err(error) = editor.load_history(&history) {
+            log::warn!("failed to load history: {}: {error}", history.display());
+        }
+    }
+
+    fs::create_dir_all(&data_dir)?;
+
+    loop {
+        let line = match editor.readline("> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(error.into()),
+        };
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        // NB: history is saved after every line, so that it isn't lost if the
+        // session ends in any other way than at the end of input.
+        editor.add_history_entry(line.as_str())?;
+        editor.save_history(&history)?;
+
+        let len = line.len();
+        let mut files = SimpleFiles::new();
+        let id = files.add("<in>".to_owned(), line);
+        run(out, opts, db, ctx, &files, id, 0..len)?;
+    }
+
+    Ok(())
+}
+
+/// Run a single query and print its results.
+///
+/// The query is the given range of a source file, so that diagnostics point
+/// to the correct line and column. Returns `false` if any result failed.
+fn run(
+    out: &mut StandardStream,
+    opts: &Opts,
+    db: &Db,
+    ctx: &mut Context,
+    files: &SimpleFiles<String, String>,
+    id: usize,
+    range: Range<usize>,
+) -> anyhow::Result<bool> {
     let options = anything::Options::default();
     let mut descriptions = Vec::new();
 
@@ -51,69 +536,421 @@ fn main() -> anyhow::Result<()> {
         options
     };
 
-    let parsed = anything::parse(files.source(id)?)?;
+    let options = if opts.candidates {
+        options.candidates()
+    } else {
+        options
+    };
+
+    let options = if opts.no_auto {
+        options
+    } else {
+        options.auto_units()
+    };
+
+    let offset = range.start;
+    let parsed = anything::parse(&files.source(id)?[range])?;
 
     if opts.syntax {
-        parsed.emit(&mut out)?;
+        parsed.emit(out)?;
     }
 
-    for value in anything::query(&parsed, &db, options, &mut descriptions) {
+    let values = anything::query(&parsed, db, ctx, options, &mut descriptions).collect::<Vec<_>>();
+    let spec = display_spec(opts);
+
+    let mut success = values.iter().all(|value| value.is_ok());
+
+    match opts.format {
+        Format::Text => {
+            success &= emit_text(out, opts, db, files, id, offset, values, descriptions)?;
+        }
+        Format::Json => {
+            let document = Document {
+                results: values
+                    .iter()
+                    .map(|v| output_result(v, &spec, offset))
+                    .collect(),
+                descriptions: descriptions
+                    .iter()
+                    .map(|d| output_description(db, d))
+                    .collect(),
+            };
+
+            serde_json::to_writer(&mut *out, &document)?;
+            writeln!(out)?;
+        }
+        Format::Ndjson => {
+            let results = values.iter().map(|v| output_result(v, &spec, offset));
+            let descriptions = descriptions.iter().map(|d| output_description(db, d));
+
+            for output in results.chain(descriptions) {
+                serde_json::to_writer(&mut *out, &output)?;
+                writeln!(out)?;
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Emit results and descriptions as human readable text.
+///
+/// Returns `false` if a result was refused.
+#[allow(clippy::too_many_arguments)]
+fn emit_text(
+    out: &mut StandardStream,
+    opts: &Opts,
+    db: &Db,
+    files: &SimpleFiles<String, String>,
+    id: usize,
+    offset: usize,
+    values: Vec<Result<Numeric, anything::Error>>,
+    descriptions: Vec<Description>,
+) -> anyhow::Result<bool> {
+    let mut success = true;
+    let config = codespan_reporting::term::Config::default();
+
+    for value in values {
         match value {
             Ok(value) => {
-                if opts.exact {
-                    if !value.value.denom().is_one() {
-                        write!(out, "{}/{}", value.value.numer(), value.value.denom())?;
-                    } else {
-                        write!(out, "{}", value.value.numer())?;
-                    }
-                } else {
-                    let mut spec = DisplaySpec::default();
+                if opts.exact && !value.exact {
+                    let diagnostic = Diagnostic::error().with_message(
+                        "result is approximate and has no exact representation (--exact)",
+                    );
+                    term::emit_to_io_write(out, &config, files, &diagnostic)?;
+                    success = false;
+                    continue;
+                }
+
+                if !value.exact {
+                    write!(out, "≈ ")?;
+                }
+
+                if let Some(instant) = value.display_instant() {
+                    writeln!(out, "{instant}")?;
+                    continue;
+                }
+
+                if let Some(parts) = value.parts() {
+                    for (n, (part, unit)) in parts.iter().enumerate() {
+                        if n > 0 {
+                            write!(out, " ")?;
+                        }
 
-                    spec.limit = 12;
-                    spec.exponent_limit = 12;
-                    spec.show_continuation = true;
+                        write_rational(out, opts, part)?;
+                        write_unit(out, part, unit)?;
+                    }
 
-                    write!(out, "{}", value.value.display(&spec))?;
+                    writeln!(out)?;
+                    continue;
                 }
 
-                if value.unit.has_numerator() {
-                    write!(out, " ")?;
+                let spec = display_spec(opts);
+
+                match (&value.uncertainty, value.display_uncertainty(&spec)) {
+                    (Some(..), Some(uncertain))
+                        if !opts.exact && spec.mode == DisplayMode::Auto =>
+                    {
+                        write!(out, "{uncertain}")?;
+                    }
+                    (Some(uncertainty), _) if !opts.exact && !uncertainty.is_zero() => {
+                        write!(
+                            out,
+                            "{} ± {}",
+                            value.value.display(&spec),
+                            uncertainty.display(&spec)
+                        )?;
+                    }
+                    _ => {
+                        write_rational(out, opts, &value.value)?;
+                    }
                 }
 
-                let disp = value.unit.display(!value.value.is_one());
-                writeln!(out, "{disp}")?;
+                write_unit(out, &value.value, &value.unit)?;
+                writeln!(out)?;
             }
             Err(e) => {
-                let labels = vec![Label::primary(id, e.range()).with_message(e.to_string())];
+                let range = e.range();
+                let range = range.start + offset..range.end + offset;
+                let labels = vec![Label::primary(id, range).with_message(e.to_string())];
                 let diagnostic = Diagnostic::error()
                     .with_message(e.to_string())
                     .with_labels(labels);
-                term::emit_to_io_write(&mut out, &config, &files, &diagnostic)?;
+                term::emit_to_io_write(out, &config, files, &diagnostic)?;
             }
         }
     }
 
+    let (candidates, descriptions): (Vec<_>, Vec<_>) = descriptions
+        .into_iter()
+        .partition(|d| matches!(d, Description::Candidates(..)));
+
     if !descriptions.is_empty() {
         writeln!(out, "# Description of constants used (--describe):")?;
 
         for description in descriptions {
             match description {
-                anything::Description::Constant(query, c) => {
+                Description::Constant(query, c) => {
                     write!(out, "{:?} => {}", query, c.description)?;
-
-                    if let Some(s) = c.source.and_then(|id| db.get_source(id)) {
-                        if let Some(url) = &s.url {
-                            write!(out, " ({}) <{}>", s.description, url)?;
-                        } else {
-                            write!(out, "({})", s.description)?;
-                        }
-                    }
-
+                    write_source(out, c.source.and_then(|id| db.get_source(id)))?;
                     writeln!(out)?;
                 }
+                Description::ExchangeRate(rate) => {
+                    write!(
+                        out,
+                        "{:?} => 1 EUR = {} {} as of {}",
+                        rate.code,
+                        rate.rate.display(&DisplaySpec::default()),
+                        rate.code,
+                        rate.date
+                    )?;
+                    write_source(out, rate.source.and_then(|id| db.get_source(id)))?;
+                    writeln!(out)?;
+                }
+                Description::Candidates(..) => {}
             }
         }
     }
 
+    if !candidates.is_empty() {
+        writeln!(out, "# Candidates for looked up constants (--candidates):")?;
+
+        for description in candidates {
+            let Description::Candidates(query, candidates) = description else {
+                continue;
+            };
+
+            writeln!(out, "{query:?}:")?;
+
+            for c in candidates {
+                writeln!(out, "  {:.3} {}", c.score, c.constant.description)?;
+            }
+        }
+    }
+
+    Ok(success)
+}
+
+/// Write a rational number, either exactly as a fraction (--exact) or as a
+/// decimal.
+fn write_rational(out: &mut StandardStream, opts: &Opts, value: &Rational) -> io::Result<()> {
+    if !opts.exact {
+        return write!(out, "{}", value.display(&display_spec(opts)));
+    }
+
+    if !value.denom().is_one() {
+        write!(out, "{}/{}", value.numer(), value.denom())
+    } else {
+        write!(out, "{}", value.numer())
+    }
+}
+
+/// Write the unit following a value.
+fn write_unit(out: &mut StandardStream, value: &Rational, unit: &Compound) -> io::Result<()> {
+    if unit.has_numerator() {
+        write!(out, " ")?;
+    }
+
+    write!(out, "{}", unit.display(!value.is_one()))
+}
+
+/// The display specification used for decimal renderings of values.
+fn display_spec(opts: &Opts) -> DisplaySpec {
+    let mut spec = DisplaySpec::default();
+    spec.limit = 12;
+    spec.exponent_limit = 12;
+    spec.show_continuation = true;
+
+    spec.mode = match (
+        opts.sig_figs,
+        opts.decimals,
+        opts.scientific,
+        opts.engineering,
+    ) {
+        (Some(n), ..) => DisplayMode::Significant(n),
+        (_, Some(n), ..) => DisplayMode::Fixed(n),
+        (.., Some(n), _) => DisplayMode::Scientific(n),
+        (.., Some(n)) => DisplayMode::Engineering(n),
+        _ => DisplayMode::Auto,
+    };
+
+    spec
+}
+
+/// The output of a single query in JSON (--format json).
+#[derive(Serialize)]
+struct Document<'a> {
+    results: Vec<Output<'a>>,
+    descriptions: Vec<Output<'a>>,
+}
+
+/// A single machine readable output.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Output<'a> {
+    Value {
+        numer: String,
+        denom: String,
+        decimal: String,
+        exact: bool,
+        uncertainty: Option<String>,
+        unit: OutputUnit,
+        parts: Option<Vec<OutputPart>>,
+        instant: Option<String>,
+    },
+    Error {
+        message: String,
+        start: usize,
+        end: usize,
+    },
+    Constant {
+        query: &'a str,
+        description: &'a str,
+        source: Option<&'a Source>,
+    },
+    ExchangeRate {
+        code: &'a str,
+        rate: String,
+        date: &'a str,
+        source: Option<&'a Source>,
+    },
+    Candidates {
+        query: &'a str,
+        candidates: Vec<OutputCandidate<'a>>,
+    },
+}
+
+/// A constant in the fact database (any search, any list).
+#[derive(Serialize)]
+struct OutputConstant<'a> {
+    score: Option<f32>,
+    tokens: &'a [Box<str>],
+    entity: Option<&'a str>,
+    attribute: Option<&'a str>,
+    description: &'a str,
+    value: Output<'a>,
+    source: Option<&'a Source>,
+}
+
+/// A single candidate considered when looking up a constant.
+#[derive(Serialize)]
+struct OutputCandidate<'a> {
+    score: f32,
+    description: &'a str,
+    source: Option<&'a Source>,
+}
+
+/// A single part of a value broken down into mixed units.
+#[derive(Serialize)]
+struct OutputPart {
+    numer: String,
+    denom: String,
+    decimal: String,
+    unit: String,
+}
+
+/// A unit together with the base dimensions it is made up of.
+#[derive(Serialize)]
+struct OutputUnit {
+    name: String,
+    base: BTreeMap<String, i32>,
+}
+
+fn output_result<'a>(
+    value: &'a Result<Numeric, anything::Error>,
+    spec: &DisplaySpec,
+    offset: usize,
+) -> Output<'a> {
+    match value {
+        Ok(value) => {
+            let (_, powers) = value.unit.base_units();
+
+            let mut spec = spec.clone();
+            spec.show_continuation = false;
+
+            let base = powers
+                .iter()
+                .map(|(unit, power)| {
+                    let unit = Compound::from_iter([(unit, (1, 0))]);
+                    (unit.to_string(), power)
+                })
+                .collect();
+
+            Output::Value {
+                numer: value.value.numer().to_string(),
+                denom: value.value.denom().to_string(),
+                decimal: value.value.display(&spec).to_string(),
+                exact: value.exact,
+                uncertainty: value
+                    .uncertainty
+                    .as_ref()
+                    .map(|u| u.display(&spec).to_string()),
+                unit: OutputUnit {
+                    name: value.unit.to_string(),
+                    base,
+                },
+                parts: value.parts().map(|parts| {
+                    parts
+                        .into_iter()
+                        .map(|(part, unit)| OutputPart {
+                            numer: part.numer().to_string(),
+                            denom: part.denom().to_string(),
+                            decimal: part.display(&spec).to_string(),
+                            unit: unit.to_string(),
+                        })
+                        .collect()
+                }),
+                instant: value.display_instant(),
+            }
+        }
+        Err(e) => {
+            let range = e.range();
+
+            Output::Error {
+                message: e.to_string(),
+                start: range.start + offset,
+                end: range.end + offset,
+            }
+        }
+    }
+}
+
+fn output_description<'a>(db: &'a Db, description: &'a Description) -> Output<'a> {
+    match description {
+        Description::Constant(query, c) => Output::Constant {
+            query,
+            description: &c.description,
+            source: c.source.and_then(|id| db.get_source(id)),
+        },
+        Description::ExchangeRate(rate) => Output::ExchangeRate {
+            code: &rate.code,
+            rate: rate.rate.display(&DisplaySpec::default()).to_string(),
+            date: &rate.date,
+            source: rate.source.and_then(|id| db.get_source(id)),
+        },
+        Description::Candidates(query, candidates) => Output::Candidates {
+            query,
+            candidates: candidates
+                .iter()
+                .map(|c| OutputCandidate {
+                    score: c.score,
+                    description: &c.constant.description,
+                    source: c.constant.source.and_then(|id| db.get_source(id)),
+                })
+                .collect(),
+        },
+    }
+}
+
+/// Write the source of a description, if there is one.
+fn write_source(out: &mut StandardStream, source: Option<&Source>) -> anyhow::Result<()> {
+    if let Some(s) = source {
+        if let Some(url) = &s.url {
+            write!(out, " ({}) <{}>", s.description, url)?;
+        } else {
+            write!(out, " ({})", s.description)?;
+        }
+    }
+
     Ok(())
 }
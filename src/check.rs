@@ -0,0 +1,314 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::Result;
+use hashbrown::HashMap;
+use num::Zero;
+
+use crate::config;
+use crate::currency::{Rates, CURRENCIES_BIN_GZ};
+use crate::db::{self, Constant, Db, Doc, Sources, SOURCES_BIN_GZ};
+use crate::rational::Rational;
+
+/// Values whose magnitude is outside of `10^±LIMIT` are implausible.
+const LIMIT: i32 = 60;
+
+/// How severe a problem found in the database is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Severity {
+    /// The database is broken, like a constant referring to a source which
+    /// doesn't exist.
+    Error,
+    /// Something is likely to confuse lookups, like a constant which can't be
+    /// found by searching for its own tokens.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in the database, see [check].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Problem {
+    /// How severe the problem is.
+    pub severity: Severity,
+    /// The name of the asset the problem was found in, like
+    /// `astronomics.bin.gz`.
+    pub asset: Box<str>,
+    /// A description of the problem.
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.severity, self.asset, self.message)
+    }
+}
+
+/// Check the database which is embedded in this crate.
+///
+/// Every asset is loaded the same way as when the search index is built, and
+/// is checked for constants which collide with each other, refer to sources
+/// which don't exist, or have implausible values. Constants whose tokens can't
+/// be searched for are errors, while those which searching for their own
+/// tokens doesn't find are reported as warnings.
+///
+/// ```
+/// use anything::Severity;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// for problem in anything::check()? {
+///     if problem.severity == Severity::Error {
+///         println!("{problem}");
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+pub fn check() -> Result<Vec<Problem>> {
+    let config = config::open()?;
+    let mut problems = Problems::default();
+
+    let sources: Sources = match config.get_asset(SOURCES_BIN_GZ) {
+        Some(file) => match db::load_bytes(file.data.as_ref()) {
+            Ok(sources) => sources,
+            Err(error) => {
+                problems.error(SOURCES_BIN_GZ, format!("cannot be loaded: {error}"));
+                Sources::default()
+            }
+        },
+        None => {
+            problems.error(SOURCES_BIN_GZ, "is missing".into());
+            Sources::default()
+        }
+    };
+
+    if sources.map.len() != sources.sources.len() {
+        problems.error(SOURCES_BIN_GZ, "has duplicate source ids".into());
+    }
+
+    match Rates::embedded() {
+        Ok(rates) => {
+            for day in rates.days() {
+                if let Some(id) = day.source.filter(|id| !sources.map.contains_key(id)) {
+                    problems.error(
+                        CURRENCIES_BIN_GZ,
+                        format!("rates for {} refer to missing source {id}", day.date),
+                    );
+                }
+
+                for (code, rate) in &day.rates {
+                    if *rate <= Rational::zero() {
+                        problems.error(
+                            CURRENCIES_BIN_GZ,
+                            format!("{code} on {} has the rate {}", day.date, display(rate)),
+                        );
+                    }
+                }
+            }
+        }
+        Err(error) => {
+            problems.error(CURRENCIES_BIN_GZ, format!("cannot be loaded: {error}"));
+        }
+    }
+
+    let mut constants = Vec::new();
+
+    for name in config.assets() {
+        if name == SOURCES_BIN_GZ || name == CURRENCIES_BIN_GZ {
+            continue;
+        }
+
+        let Some(file) = config.get_asset(name.as_ref()) else {
+            continue;
+        };
+
+        let doc: Doc = match db::load_bytes(file.data.as_ref()) {
+            Ok(doc) => doc,
+            Err(error) => {
+                problems.error(&name, format!("cannot be loaded: {error}"));
+                continue;
+            }
+        };
+
+        // NB: constants are decoded from what is stored in the index.
+        for c in doc.constants {
+            let decoded =
+                serde_cbor::to_vec(&c).and_then(|bytes| serde_cbor::from_slice::<Constant>(&bytes));
+
+            match decoded {
+                Ok(constant) => constants.push((name.clone(), constant)),
+                Err(error) => {
+                    problems.error(&name, format!("{:?} cannot be decoded: {error}", c.tokens));
+                }
+            }
+        }
+    }
+
+    let mut tokens = HashMap::<Vec<String>, &Constant>::new();
+    let mut attributes = HashMap::<(String, String), &Constant>::new();
+
+    for (name, c) in &constants {
+        let d = &c.description;
+
+        if let Some(id) = c.source.filter(|id| !sources.map.contains_key(id)) {
+            problems.error(name, format!("`{d}` refers to missing source {id}"));
+        }
+
+        if c.tokens.is_empty() {
+            problems.error(name, format!("`{d}` has no tokens"));
+        } else if let Some(other) = tokens.insert(token_set(c), c) {
+            let message = format!("`{d}` has the same tokens as `{}`", other.description);
+
+            // NB: attributes of different entities can still be looked up
+            // exactly, like `less_developed_regions.population`.
+            match (&c.entity, &other.entity) {
+                (Some(a), Some(b)) if db::normalize(a) != db::normalize(b) => {
+                    problems.warning(name, message);
+                }
+                _ => problems.error(name, message),
+            }
+        }
+
+        if let (Some(entity), Some(attribute)) = (&c.entity, &c.attribute) {
+            let key = (db::normalize(entity), db::normalize(attribute));
+
+            if let Some(other) = attributes.insert(key, c) {
+                problems.error(
+                    name,
+                    format!(
+                        "`{d}` is the same attribute of an entity as `{}`",
+                        other.description
+                    ),
+                );
+            }
+        } else if c.entity.is_some() || c.attribute.is_some() {
+            problems.error(name, format!("`{d}` needs both an entity and an attribute"));
+        }
+
+        for value in [&c.value].into_iter().chain(c.series.values()) {
+            if !is_plausible(value) {
+                problems.error(
+                    name,
+                    format!("`{d}` has the implausible value {}", display(value)),
+                );
+            }
+        }
+
+        match &c.uncertainty {
+            Some(u) if *u < Rational::zero() => {
+                problems.error(name, format!("`{d}` has a negative uncertainty"));
+            }
+            Some(u) if !c.value.is_zero() && *u > c.value.abs() => {
+                problems.warning(name, format!("`{d}` is more uncertain than its value"));
+            }
+            _ => {}
+        }
+    }
+
+    let db = Db::in_memory()?;
+
+    for (name, c) in &constants {
+        if c.tokens.is_empty() {
+            continue;
+        }
+
+        let query = c.tokens.join(" ");
+
+        let candidates = match db.search(&query, 1) {
+            Ok(candidates) => candidates,
+            Err(error) => {
+                problems.error(name, format!("`{query}` cannot be searched for: {error}"));
+                continue;
+            }
+        };
+
+        let Some(best) = candidates.into_iter().next() else {
+            problems.warning(name, format!("`{query}` doesn't find `{}`", c.description));
+            continue;
+        };
+
+        // NB: constants with the same tokens are reported as errors above.
+        if token_set(&best.constant) != token_set(c) {
+            problems.warning(
+                name,
+                format!(
+                    "`{query}` finds `{}` instead of `{}`",
+                    best.constant.description, c.description
+                ),
+            );
+        }
+    }
+
+    Ok(problems.into_vec())
+}
+
+/// Collected problems, ordered by asset.
+#[derive(Default)]
+struct Problems {
+    problems: BTreeMap<Box<str>, Vec<Problem>>,
+}
+
+impl Problems {
+    fn error(&mut self, asset: &str, message: String) {
+        self.push(Severity::Error, asset, message);
+    }
+
+    fn warning(&mut self, asset: &str, message: String) {
+        self.push(Severity::Warning, asset, message);
+    }
+
+    fn push(&mut self, severity: Severity, asset: &str, message: String) {
+        self.problems
+            .entry(asset.into())
+            .or_default()
+            .push(Problem {
+                severity,
+                asset: asset.into(),
+                message,
+            });
+    }
+
+    fn into_vec(self) -> Vec<Problem> {
+        self.problems.into_values().flatten().collect()
+    }
+}
+
+/// The tokens of a constant, lowercased and sorted so that they can be
+/// compared.
+fn token_set(c: &Constant) -> Vec<String> {
+    let mut tokens = c
+        .tokens
+        .iter()
+        .map(|t| t.to_lowercase())
+        .collect::<Vec<_>>();
+
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+/// Display a value in the default way.
+fn display(value: &Rational) -> String {
+    value.display(&Default::default()).to_string()
+}
+
+/// Test if a value has a plausible magnitude. Zero is plausible, like the
+/// melting point of ice in degrees Celsius.
+fn is_plausible(value: &Rational) -> bool {
+    if value.is_zero() {
+        return true;
+    }
+
+    let value = value.abs();
+    let digits = |n: &num::BigInt| n.to_string().len() as i32;
+    let magnitude = digits(value.numer()) - digits(value.denom());
+    (-LIMIT..=LIMIT).contains(&magnitude)
+}
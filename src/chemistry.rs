@@ -0,0 +1,228 @@
+//! Chemical elements and formulas.
+//!
+//! Elements are named the same way as the entities in the database which hold
+//! their atomic mass, density and melting point.
+
+/// The symbol and name of every element, by atomic number.
+static ELEMENTS: [(&str, &str); 118] = [
+    ("H", "Hydrogen"),
+    ("He", "Helium"),
+    ("Li", "Lithium"),
+    ("Be", "Beryllium"),
+    ("B", "Boron"),
+    ("C", "Carbon"),
+    ("N", "Nitrogen"),
+    ("O", "Oxygen"),
+    ("F", "Fluorine"),
+    ("Ne", "Neon"),
+    ("Na", "Sodium"),
+    ("Mg", "Magnesium"),
+    ("Al", "Aluminum"),
+    ("Si", "Silicon"),
+    ("P", "Phosphorus"),
+    ("S", "Sulfur"),
+    ("Cl", "Chlorine"),
+    ("Ar", "Argon"),
+    ("K", "Potassium"),
+    ("Ca", "Calcium"),
+    ("Sc", "Scandium"),
+    ("Ti", "Titanium"),
+    ("V", "Vanadium"),
+    ("Cr", "Chromium"),
+    ("Mn", "Manganese"),
+    ("Fe", "Iron"),
+    ("Co", "Cobalt"),
+    ("Ni", "Nickel"),
+    ("Cu", "Copper"),
+    ("Zn", "Zinc"),
+    ("Ga", "Gallium"),
+    ("Ge", "Germanium"),
+    ("As", "Arsenic"),
+    ("Se", "Selenium"),
+    ("Br", "Bromine"),
+    ("Kr", "Krypton"),
+    ("Rb", "Rubidium"),
+    ("Sr", "Strontium"),
+    ("Y", "Yttrium"),
+    ("Zr", "Zirconium"),
+    ("Nb", "Niobium"),
+    ("Mo", "Molybdenum"),
+    ("Tc", "Technetium"),
+    ("Ru", "Ruthenium"),
+    ("Rh", "Rhodium"),
+    ("Pd", "Palladium"),
+    ("Ag", "Silver"),
+    ("Cd", "Cadmium"),
+    ("In", "Indium"),
+    ("Sn", "Tin"),
+    ("Sb", "Antimony"),
+    ("Te", "Tellurium"),
+    ("I", "Iodine"),
+    ("Xe", "Xenon"),
+    ("Cs", "Cesium"),
+    ("Ba", "Barium"),
+    ("La", "Lanthanum"),
+    ("Ce", "Cerium"),
+    ("Pr", "Praseodymium"),
+    ("Nd", "Neodymium"),
+    ("Pm", "Promethium"),
+    ("Sm", "Samarium"),
+    ("Eu", "Europium"),
+    ("Gd", "Gadolinium"),
+    ("Tb", "Terbium"),
+    ("Dy", "Dysprosium"),
+    ("Ho", "Holmium"),
+    ("Er", "Erbium"),
+    ("Tm", "Thulium"),
+    ("Yb", "Ytterbium"),
+    ("Lu", "Lutetium"),
+    ("Hf", "Hafnium"),
+    ("Ta", "Tantalum"),
+    ("W", "Tungsten"),
+    ("Re", "Rhenium"),
+    ("Os", "Osmium"),
+    ("Ir", "Iridium"),
+    ("Pt", "Platinum"),
+    ("Au", "Gold"),
+    ("Hg", "Mercury"),
+    ("Tl", "Thallium"),
+    ("Pb", "Lead"),
+    ("Bi", "Bismuth"),
+    ("Po", "Polonium"),
+    ("At", "Astatine"),
+    ("Rn", "Radon"),
+    ("Fr", "Francium"),
+    ("Ra", "Radium"),
+    ("Ac", "Actinium"),
+    ("Th", "Thorium"),
+    ("Pa", "Protactinium"),
+    ("U", "Uranium"),
+    ("Np", "Neptunium"),
+    ("Pu", "Plutonium"),
+    ("Am", "Americium"),
+    ("Cm", "Curium"),
+    ("Bk", "Berkelium"),
+    ("Cf", "Californium"),
+    ("Es", "Einsteinium"),
+    ("Fm", "Fermium"),
+    ("Md", "Mendelevium"),
+    ("No", "Nobelium"),
+    ("Lr", "Lawrencium"),
+    ("Rf", "Rutherfordium"),
+    ("Db", "Dubnium"),
+    ("Sg", "Seaborgium"),
+    ("Bh", "Bohrium"),
+    ("Hs", "Hassium"),
+    ("Mt", "Meitnerium"),
+    ("Ds", "Darmstadtium"),
+    ("Rg", "Roentgenium"),
+    ("Cn", "Copernicium"),
+    ("Nh", "Nihonium"),
+    ("Fl", "Flerovium"),
+    ("Mc", "Moscovium"),
+    ("Lv", "Livermorium"),
+    ("Ts", "Tennessine"),
+    ("Og", "Oganesson"),
+];
+
+/// Groups can't be nested deeper than this, like `((CH3)3C)2O`.
+const MAX_DEPTH: usize = 8;
+
+/// Get the name of the element with the given symbol, like `Sodium` for `Na`.
+pub(crate) fn element(symbol: &str) -> Option<&'static str> {
+    let (_, name) = ELEMENTS.iter().find(|(s, _)| *s == symbol)?;
+    Some(name)
+}
+
+/// Parse a chemical formula like `H2SO4` or `Ca(OH)2` into the number of atoms
+/// of each element in it, by name and in order of first appearance.
+///
+/// Returns `None` if the string isn't a formula, which is the case for most
+/// units since element symbols start with an upper case letter.
+pub(crate) fn parse(formula: &str) -> Option<Vec<(&'static str, u32)>> {
+    let mut it = formula.char_indices().peekable();
+    // NB: each group is the elements in it, together with their counts.
+    let mut groups = vec![Vec::<(&'static str, u32)>::new()];
+
+    while let Some((start, c)) = it.next() {
+        match c {
+            'A'..='Z' => {
+                let mut end = start + 1;
+
+                while let Some(&(n, c)) = it.peek() {
+                    if !c.is_ascii_lowercase() {
+                        break;
+                    }
+
+                    it.next();
+                    end = n + 1;
+                }
+
+                let name = element(&formula[start..end])?;
+                let count = count(&mut it)?;
+                add(groups.last_mut()?, name, count)?;
+            }
+            '(' => {
+                if groups.len() > MAX_DEPTH {
+                    return None;
+                }
+
+                groups.push(Vec::new());
+            }
+            ')' => {
+                let group = groups.pop()?;
+
+                if group.is_empty() {
+                    return None;
+                }
+
+                let count = count(&mut it)?;
+                let parent = groups.last_mut()?;
+
+                for (name, n) in group {
+                    add(parent, name, n.checked_mul(count)?)?;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    let [elements] = <[_; 1]>::try_from(groups).ok()?;
+
+    if elements.is_empty() {
+        return None;
+    }
+
+    Some(elements)
+}
+
+/// Parse the number of atoms following an element or a group, which is one if
+/// it's omitted.
+fn count(it: &mut std::iter::Peekable<std::str::CharIndices<'_>>) -> Option<u32> {
+    let mut count = None::<u32>;
+
+    while let Some(&(_, c)) = it.peek() {
+        let Some(digit) = c.to_digit(10) else {
+            break;
+        };
+
+        it.next();
+        count = Some(count.unwrap_or(0).checked_mul(10)?.checked_add(digit)?);
+    }
+
+    match count {
+        Some(0) => None,
+        Some(count) => Some(count),
+        None => Some(1),
+    }
+}
+
+/// Add atoms of an element to a group.
+fn add(group: &mut Vec<(&'static str, u32)>, name: &'static str, count: u32) -> Option<()> {
+    match group.iter_mut().find(|(n, _)| *n == name) {
+        Some((_, n)) => *n = n.checked_add(count)?,
+        None => group.push((name, count)),
+    }
+
+    Some(())
+}
@@ -1,8 +1,10 @@
 use crate::error::ErrorKind;
 use crate::powers::Powers;
+use crate::prefix::Prefix;
 use crate::rational::Rational;
 use crate::syntax::parser::{Parser, Syntax};
-use crate::unit::{Conversion, Unit};
+use crate::unit::{Conversion, Unit, PI};
+use crate::unit_registry::UnitRegistry;
 use crate::Error;
 use serde::{Deserialize, Serialize};
 use std::collections::{btree_map, BTreeMap};
@@ -10,8 +12,19 @@ use std::fmt;
 use std::iter::FromIterator;
 use syntree::Span;
 
+/// The largest power a unit in a compound may be raised to.
+///
+/// Derived units multiply their powers when they are broken down into base
+/// units, so this is kept well below `i32::MAX`.
+const MAX_POWER: i32 = i16::MAX as i32;
+
 #[non_exhaustive]
-pub(crate) struct CompoundError;
+pub(crate) enum CompoundError {
+    /// The units can't be converted between each other.
+    Conversion,
+    /// The power of a unit would be too large.
+    PowerTooLarge,
+}
 
 /// The data for a base.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -106,6 +119,22 @@ impl Compound {
         }
     }
 
+    /// Iterate over the units in this compound.
+    pub(crate) fn units(&self) -> impl Iterator<Item = Unit> + '_ {
+        self.names.keys().cloned()
+    }
+
+    /// Get the unit and its state if this compound consists of a single
+    /// unit.
+    pub(crate) fn single(&self) -> Option<(Unit, State)> {
+        let mut it = self.names.iter();
+
+        match (it.next(), it.next()) {
+            (Some((unit, state)), None) => Some((unit.clone(), *state)),
+            _ => None,
+        }
+    }
+
     /// Test if this unit has a numerator.
     pub fn has_numerator(&self) -> bool {
         self.names.values().any(|s| s.power > 0)
@@ -129,6 +158,75 @@ impl Compound {
         meter == Some(1) && second == Some(-2)
     }
 
+    /// Test if this unit is a mass unit.
+    pub(crate) fn is_mass(&self) -> bool {
+        self.is_base(Unit::KiloGram)
+    }
+
+    /// Test if this unit is an amount of substance unit.
+    pub(crate) fn is_amount(&self) -> bool {
+        self.is_base(Unit::Mole)
+    }
+
+    /// Test if this unit is the given base unit, to the first power.
+    fn is_base(&self, unit: Unit) -> bool {
+        let (_, bases) = self.base_units();
+        bases.len() == 1 && bases.get(unit) == Some(1)
+    }
+
+    /// Raise every unit in this compound to the given power, unless the
+    /// resulting power is too large.
+    pub(crate) fn powi(&self, power: i32) -> Option<Compound> {
+        self.names
+            .iter()
+            .map(|(unit, state)| {
+                Some((
+                    unit.clone(),
+                    (checked_power(state.power, power)?, state.prefix),
+                ))
+            })
+            .collect()
+    }
+
+    /// Take the `n`th root of this unit, which is only possible if the power
+    /// of every unit is divisible by `n`.
+    pub(crate) fn root(&self, n: u32) -> Option<Compound> {
+        let n = i32::try_from(n).ok()?;
+
+        if n == 0 || self.names.values().any(|state| state.power % n != 0) {
+            return None;
+        }
+
+        Some(
+            self.names
+                .iter()
+                .map(|(unit, state)| (unit.clone(), (state.power / n, state.prefix)))
+                .collect(),
+        )
+    }
+
+    /// Get the unit expressed only in terms of its base units.
+    pub(crate) fn base(&self) -> Compound {
+        let (_, powers) = self.base_units();
+        powers
+            .iter()
+            .map(|(unit, power)| (unit, (power, 0)))
+            .collect()
+    }
+
+    /// Get the power of `π` which is involved when converting this unit into
+    /// its base units.
+    ///
+    /// Conversions between units with the same power of `π` are exact, since
+    /// the approximations of `π` cancel out.
+    pub(crate) fn pi_power(&self) -> i32 {
+        self.names
+            .iter()
+            .filter(|(unit, _)| matches!(unit.conversion(), Some(Conversion::Pi(..))))
+            .map(|(_, state)| state.power)
+            .sum()
+    }
+
     /// Calculate the factor for coercing one unit to another.
     pub(crate) fn factor(&self, other: &Self, value: &mut Rational) -> Result<bool, CompoundError> {
         if self.is_empty() || other.is_empty() {
@@ -154,7 +252,7 @@ impl Compound {
         }
 
         for (name, state) in &other.names {
-            *value *= Rational::new(10u32, 1u32).pow(state.prefix * state.power);
+            *value *= Prefix::factor(state.prefix, state.power);
 
             if let Some(conversion) = name.conversion() {
                 apply_conversion(state.power, value, conversion)?;
@@ -166,7 +264,7 @@ impl Compound {
                 apply_conversion(-state.power, value, conversion)?;
             }
 
-            *value /= Rational::new(10u32, 1u32).pow(state.prefix * state.power);
+            *value /= Prefix::factor(state.prefix, state.power);
         }
 
         Ok(true)
@@ -182,11 +280,7 @@ impl Compound {
     ) -> Result<Self, CompoundError> {
         if self.is_empty() || other.is_empty() {
             let unit = if self.is_empty() {
-                other
-                    .names
-                    .iter()
-                    .map(|(unit, state)| (*unit, (state.power * n, state.prefix)))
-                    .collect()
+                other.powi(n).ok_or(CompoundError::PowerTooLarge)?
             } else {
                 self.clone()
             };
@@ -204,15 +298,19 @@ impl Compound {
         }
 
         for (name, power) in rhs_bases {
+            let power = checked_power(power, n).ok_or(CompoundError::PowerTooLarge)?;
+
             match names.entry(name) {
                 btree_map::Entry::Vacant(e) => {
-                    e.insert(State {
-                        power: power * n,
-                        prefix: 0,
-                    });
+                    e.insert(State { power, prefix: 0 });
                 }
                 btree_map::Entry::Occupied(mut e) => {
-                    e.get_mut().power += power * n;
+                    e.get_mut().power = e
+                        .get()
+                        .power
+                        .checked_add(power)
+                        .filter(|p| p.abs() <= MAX_POWER)
+                        .ok_or(CompoundError::PowerTooLarge)?;
 
                     if e.get().power == 0 {
                         e.remove_entry();
@@ -222,7 +320,7 @@ impl Compound {
         }
 
         for (name, state) in &self.names {
-            *lhs *= Rational::new(10u32, 1u32).pow(state.prefix * state.power);
+            *lhs *= Prefix::factor(state.prefix, state.power);
 
             if let Some(conversion) = name.conversion() {
                 apply_conversion(state.power, lhs, conversion)?;
@@ -230,7 +328,7 @@ impl Compound {
         }
 
         for (name, state) in &other.names {
-            *rhs *= Rational::new(10u32, 1u32).pow(state.prefix * state.power);
+            *rhs *= Prefix::factor(state.prefix, state.power);
 
             if let Some(conversion) = name.conversion() {
                 apply_conversion(state.power, rhs, conversion)?;
@@ -278,7 +376,7 @@ impl Compound {
                     }
                 }
 
-                match names.entry(unit) {
+                match names.entry(unit.clone()) {
                     btree_map::Entry::Vacant(e) => {
                         e.insert(State {
                             power: mod_power,
@@ -344,14 +442,24 @@ impl Compound {
         }
     }
 
-    /// Get all base units out of the current unit.
-    fn base_units(&self) -> (Vec<(Unit, i32)>, Powers) {
+    /// Get all base units out of the current unit, together with the derived
+    /// units which were expanded into them.
+    ///
+    /// ```
+    /// use anything::{Compound, Unit};
+    ///
+    /// let unit = str::parse::<Compound>("km/h").unwrap();
+    /// let (_, powers) = unit.base_units();
+    /// assert_eq!(powers.get(Unit::Meter), Some(1));
+    /// assert_eq!(powers.get(Unit::Second), Some(-1));
+    /// ```
+    pub fn base_units(&self) -> (Vec<(Unit, i32)>, Powers) {
         let mut powers = Powers::default();
         let mut derived = Vec::new();
 
         for (name, state) in &self.names {
             if name.powers(&mut powers, state.power) {
-                derived.push((*name, state.power));
+                derived.push((name.clone(), state.power));
             }
         }
 
@@ -479,7 +587,7 @@ impl std::str::FromStr for Compound {
             None => Default::default(),
         };
 
-        crate::eval::unit(s, children, Default::default())
+        crate::eval::unit(s, &UnitRegistry::default(), children, Default::default())
     }
 }
 
@@ -497,6 +605,11 @@ impl fmt::Display for Compound {
     }
 }
 
+/// Multiply a power, unless the result is larger than [`MAX_POWER`].
+fn checked_power(power: i32, n: i32) -> Option<i32> {
+    power.checked_mul(n).filter(|p| p.abs() <= MAX_POWER)
+}
+
 fn apply_conversion(
     pow: i32,
     ratio: &mut Rational,
@@ -505,7 +618,7 @@ fn apply_conversion(
     match conversion {
         Conversion::Methods(methods) => {
             if pow.abs() != 1 {
-                return Err(CompoundError);
+                return Err(CompoundError::Conversion);
             }
 
             for _ in pow..0 {
@@ -521,9 +634,15 @@ fn apply_conversion(
                 *ratio *= Rational::new(fraction.numer, fraction.denom).pow(pow);
             }
         }
+        Conversion::Pi(fraction) => {
+            if pow != 0 {
+                let pi = Rational::new(PI.numer, PI.denom);
+                *ratio *= (Rational::new(fraction.numer, fraction.denom) * pi).pow(pow);
+            }
+        }
         Conversion::Offset(fraction) => {
             if pow.abs() != 1 {
-                return Err(CompoundError);
+                return Err(CompoundError::Conversion);
             }
 
             if pow != 0 {
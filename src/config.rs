@@ -5,6 +5,8 @@ use std::borrow::Cow;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::facts::Facts;
+
 #[derive(RustEmbed)]
 #[folder = "db"]
 struct Asset;
@@ -42,11 +44,12 @@ impl Config {
 
     /// Get content for the given asset.
     pub fn get_asset(&self, name: &str) -> Option<EmbeddedFile> {
-        Asset::get(name)
+        asset(name)
     }
 
-    /// Hash all available assets so we can determine if we need to rebuild or not.
-    pub fn hash_assets(&self) -> String {
+    /// Hash all available assets and user facts so we can determine if we need
+    /// to rebuild or not.
+    pub fn hash_assets(&self, facts: &Facts) -> Result<String> {
         const SEED: u64 = 0x9a7f42b11904b426;
 
         let mut hash = twox_hash::xxhash3_128::Hasher::with_seed(SEED);
@@ -65,14 +68,34 @@ impl Config {
             }
         }
 
-        format!("{:x}", hash.finish_128())
+        facts.hash(&mut hash)?;
+        Ok(format!("{:x}", hash.finish_128()))
     }
 }
 
+/// Get content for the given embedded asset.
+pub(crate) fn asset(name: &str) -> Option<EmbeddedFile> {
+    Asset::get(name)
+}
+
+fn project_dirs() -> Result<directories::ProjectDirs> {
+    directories::ProjectDirs::from("se.tedro", "tedro", "facts")
+        .ok_or_else(|| anyhow!("project directories not supported"))
+}
+
+/// Get the directory where persistent data such as the search index and
+/// interactive history is stored.
+pub fn data_dir() -> Result<PathBuf> {
+    Ok(project_dirs()?.data_dir().to_owned())
+}
+
+/// Get the directory where user configuration such as custom units is stored.
+pub fn config_dir() -> Result<PathBuf> {
+    Ok(project_dirs()?.config_dir().to_owned())
+}
+
 pub fn open() -> Result<Config> {
-    let dirs = directories::ProjectDirs::from("se.tedro", "tedro", "facts")
-        .ok_or_else(|| anyhow!("project directories not supported"))?;
-    let data = dirs.data_dir();
+    let data = data_dir()?;
     let index_path = data.join("index");
     let meta_path = data.join("meta.json");
 
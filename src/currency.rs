@@ -0,0 +1,186 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use anyhow::{anyhow, Context, Result};
+use flate2::read::GzDecoder;
+use serde::{Deserialize, Serialize};
+
+use crate::date;
+use crate::rational::Rational;
+
+/// The name of the embedded asset containing exchange rates.
+pub(crate) const CURRENCIES_BIN_GZ: &str = "currencies.bin.gz";
+
+/// Exchange rates for a single day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Day {
+    /// The date the rates apply to, formatted as `YYYY-MM-DD`.
+    pub date: Box<str>,
+    /// The source the rates were taken from, see [Db::get_source][crate::Db::get_source].
+    #[serde(default)]
+    pub source: Option<u64>,
+    /// How many units of each currency, keyed by its ISO 4217 code, that make
+    /// up one euro.
+    pub rates: BTreeMap<Box<str>, Rational>,
+}
+
+/// The exchange rate of a single currency which was used by a query.
+#[derive(Debug, Clone)]
+pub struct ExchangeRate {
+    /// The ISO 4217 code of the currency.
+    pub code: Box<str>,
+    /// How many units of the currency that make up one euro.
+    pub rate: Rational,
+    /// The date the rate applies to, formatted as `YYYY-MM-DD`.
+    pub date: Box<str>,
+    /// The source the rate was taken from, see [Db::get_source][crate::Db::get_source].
+    pub source: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Doc<'a> {
+    #[serde(default, borrow)]
+    days: Vec<DayDefinition<'a>>,
+}
+
+#[derive(Deserialize)]
+struct DayDefinition<'a> {
+    #[serde(borrow)]
+    date: Cow<'a, str>,
+    #[serde(default, borrow)]
+    rates: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+}
+
+/// A table of currency exchange rates relative to the euro, by date.
+///
+/// Rates are shipped with the database, and can be overridden from a TOML
+/// document:
+///
+/// ```
+/// use anything::Rates;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut rates = Rates::new();
+///
+/// rates.load_toml(r#"
+/// [[days]]
+/// date = "2024-12-31"
+/// rates = { USD = "1.0389", SEK = "11.459" }
+///
+/// [[days]]
+/// date = "2025-01-31"
+/// rates = { USD = "1.0393" }
+/// "#)?;
+///
+/// assert_eq!(&*rates.as_of(None)?.date, "2025-01-31");
+/// assert_eq!(&*rates.as_of(Some("2025-01-15"))?.date, "2024-12-31");
+/// assert!(rates.as_of(Some("2024-01-01")).is_err());
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Rates {
+    /// Exchange rates ordered by date.
+    days: Vec<Day>,
+}
+
+impl Rates {
+    /// Construct a new empty table of rates.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load the exchange rates which are embedded in the database.
+    pub fn embedded() -> Result<Self> {
+        let Some(file) = crate::config::asset(CURRENCIES_BIN_GZ) else {
+            return Ok(Self::default());
+        };
+
+        let bytes = GzDecoder::new(Cursor::new(&file.data[..]));
+        Ok(serde_cbor::from_reader(bytes)?)
+    }
+
+    /// The exchange rates, ordered by date.
+    pub(crate) fn days(&self) -> &[Day] {
+        &self.days
+    }
+
+    /// Insert exchange rates for the given day.
+    ///
+    /// Rates for a date which already exists are merged with the existing
+    /// ones, replacing the rates of the same currencies.
+    pub fn insert(&mut self, day: Day) -> Result<()> {
+        if !is_date(&day.date) {
+            return Err(anyhow!("bad date `{}`, expected `YYYY-MM-DD`", day.date));
+        }
+
+        match self.days.binary_search_by(|d| d.date.cmp(&day.date)) {
+            Ok(n) => {
+                let existing = &mut self.days[n];
+                existing.source = day.source;
+                existing.rates.extend(day.rates);
+            }
+            Err(n) => {
+                self.days.insert(n, day);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load exchange rates from the given TOML document, overriding any
+    /// existing rates for the same dates and currencies.
+    ///
+    /// Rates are expressed as how many units of the currency make up one
+    /// euro.
+    pub fn load_toml(&mut self, source: &str) -> Result<()> {
+        let doc: Doc<'_> = toml::from_str(source)?;
+
+        for d in doc.days {
+            let mut rates = BTreeMap::new();
+
+            for (code, rate) in d.rates {
+                let rate = str::parse::<Rational>(&rate)
+                    .with_context(|| anyhow!("{}: {code}: bad rate `{rate}`", d.date))?;
+                rates.insert(code.as_ref().into(), rate);
+            }
+
+            self.insert(Day {
+                date: d.date.as_ref().into(),
+                source: None,
+                rates,
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the most recent exchange rates on or before the given date, or the
+    /// most recent rates available if no date is specified.
+    pub fn as_of(&self, date: Option<&str>) -> Result<&Day> {
+        let Some(date) = date else {
+            return self
+                .days
+                .last()
+                .ok_or_else(|| anyhow!("no exchange rates available"));
+        };
+
+        if !is_date(date) {
+            return Err(anyhow!("bad date `{date}`, expected `YYYY-MM-DD`"));
+        }
+
+        self.days
+            .iter()
+            .rev()
+            .find(|d| d.date.as_ref() <= date)
+            .ok_or_else(|| anyhow!("no exchange rates available on or before {date}"))
+    }
+}
+
+/// Test if the given string is a date formatted as `YYYY-MM-DD`.
+///
+/// Dates in this format can be compared lexicographically.
+fn is_date(s: &str) -> bool {
+    // NB: without a time of day, since those can't be compared as strings.
+    s.len() == 10 && date::parse(s).is_some()
+}
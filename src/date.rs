@@ -0,0 +1,229 @@
+//! Calendar dates and instants in time.
+//!
+//! Instants are represented as the number of seconds since the Unix epoch
+//! `1970-01-01T00:00:00Z`, together with the UTC offset they are displayed in.
+//! Dates are converted using the proleptic Gregorian calendar.
+
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use num::{BigInt, Integer, ToPrimitive, Zero};
+
+use crate::rational::Rational;
+
+/// The number of seconds in a day.
+const DAY: i64 = 86400;
+/// The largest number of days an instant may be from the Unix epoch, which is
+/// roughly a billion years.
+const MAX_DAYS: i64 = 365_242_500_000;
+
+/// Parse a date like `2026-10-18`, optionally followed by a time of day like
+/// `T09:30` or `T09:30:15.5` and a UTC offset like `Z` or `+02:00`.
+///
+/// Returns the number of seconds since the Unix epoch and the offset in
+/// minutes east of UTC.
+pub(crate) fn parse(s: &str) -> Option<(Rational, i32)> {
+    let b = s.as_bytes();
+
+    let year = digits(b.get(..4)?)?;
+    let month = digits(b.get(5..7)?)?;
+    let day = digits(b.get(8..10)?)?;
+
+    if b[4] != b'-' || b[7] != b'-' || !(1..=12).contains(&month) {
+        return None;
+    }
+
+    if day < 1 || day > days_in_month(year, month) {
+        return None;
+    }
+
+    let mut rest = &b[10..];
+    let mut seconds = Rational::new(0, 1);
+
+    if let [b'T', time @ ..] = rest {
+        let hour = digits(time.get(..2)?)?;
+        let minute = digits(time.get(3..5)?)?;
+
+        if time[2] != b':' || hour > 23 || minute > 59 {
+            return None;
+        }
+
+        seconds = Rational::new(hour * 3600 + minute * 60, 1);
+        rest = &time[5..];
+
+        if let [b':', time @ ..] = rest {
+            let second = digits(time.get(..2)?)?;
+
+            if second > 59 {
+                return None;
+            }
+
+            let mut len = 2;
+
+            if time.get(2) == Some(&b'.') {
+                len += 1 + time[3..].iter().take_while(|b| b.is_ascii_digit()).count();
+            }
+
+            let second = std::str::from_utf8(&time[..len]).ok()?;
+            seconds += str::parse::<Rational>(second).ok()?;
+            rest = &time[len..];
+        }
+    }
+
+    let offset = match rest {
+        [] | [b'Z'] => 0,
+        [sign @ (b'+' | b'-'), hh0, hh1, b':', mm0, mm1] => {
+            let hours = digits(&[*hh0, *hh1])?;
+            let minutes = digits(&[*mm0, *mm1])?;
+
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+
+            let offset = (hours * 60 + minutes) as i32;
+
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+
+    let days = days_from_civil(year, month, day);
+    let local = Rational::new(days * DAY, 1) + seconds;
+    Some((local - Rational::new(i64::from(offset) * 60, 1), offset))
+}
+
+/// Format an instant in time, in seconds since the Unix epoch, using the
+/// given offset in minutes east of UTC.
+///
+/// Instants at midnight UTC are formatted as a date like `2026-10-18`, and
+/// other instants like `2026-10-18T09:30:00Z` or `2026-10-18T11:30:00+02:00`.
+/// Fractions of seconds are truncated to milliseconds.
+///
+/// Returns `None` if the instant is too far from the Unix epoch, see
+/// [in_range].
+pub(crate) fn format(seconds: &Rational, offset: i32) -> Option<String> {
+    let local = seconds.clone() + Rational::new(i64::from(offset) * 60, 1);
+
+    let (whole, fraction) = local.numer().div_mod_floor(local.denom());
+    let (days, time) = whole.div_mod_floor(&BigInt::from(DAY));
+
+    let days = days.to_i64().filter(|days| days.abs() <= MAX_DAYS)?;
+    let time = time.to_i64()?;
+    let (year, month, day) = civil_from_days(days);
+
+    let mut out = String::new();
+
+    if year < 0 {
+        let _ = write!(out, "-{:04}-{month:02}-{day:02}", -year);
+    } else {
+        let _ = write!(out, "{year:04}-{month:02}-{day:02}");
+    }
+
+    if time == 0 && fraction.is_zero() && offset == 0 {
+        return Some(out);
+    }
+
+    let _ = write!(
+        out,
+        "T{:02}:{:02}:{:02}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    );
+
+    let millis = (fraction * 1000u32 / local.denom())
+        .to_u32()
+        .unwrap_or_default();
+
+    if millis > 0 {
+        let millis = format!("{millis:03}");
+        let _ = write!(out, ".{}", millis.trim_end_matches('0'));
+    }
+
+    if offset == 0 {
+        out.push('Z');
+    } else {
+        let sign = if offset < 0 { '-' } else { '+' };
+        let offset = offset.unsigned_abs();
+        let _ = write!(out, "{sign}{:02}:{:02}", offset / 60, offset % 60);
+    }
+
+    Some(out)
+}
+
+/// Test if an instant in time, in seconds since the Unix epoch, is close
+/// enough to the epoch to be formatted as a date.
+pub(crate) fn in_range(seconds: &Rational) -> bool {
+    // NB: leave a day of margin for the UTC offset used when formatting.
+    let days = seconds.numer().div_floor(&(seconds.denom() * DAY));
+    days.to_i64().is_some_and(|days| days.abs() < MAX_DAYS)
+}
+
+/// The current instant in time, with millisecond precision.
+pub(crate) fn now() -> Rational {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+
+    Rational::new(millis, 1000)
+}
+
+/// The start of the current day in UTC.
+pub(crate) fn today() -> Rational {
+    let now = now();
+    let days = now.numer().div_floor(&(now.denom() * DAY));
+    Rational::new(days * DAY, 1)
+}
+
+/// Parse exactly two or four ASCII digits.
+fn digits(b: &[u8]) -> Option<i64> {
+    b.iter().try_fold(0, |n, b| {
+        b.is_ascii_digit().then(|| n * 10 + i64::from(b - b'0'))
+    })
+}
+
+fn is_leap(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Get the number of days since `1970-01-01` for the given date.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// Get the date for the given number of days since `1970-01-01`.
+///
+/// See <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let doe = days - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
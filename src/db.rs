@@ -2,43 +2,81 @@ use anyhow::{anyhow, Context, Result};
 use flate2::read::GzDecoder;
 use hashbrown::HashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::io::Cursor;
-use tantivy::collector::TopDocs;
-use tantivy::query::{QueryParser, QueryParserError};
+use tantivy::collector::{DocSetCollector, TopDocs};
+use tantivy::query::{AllQuery, QueryParser, QueryParserError, TermQuery};
 use tantivy::schema::{
-    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, STORED,
+    Field, IndexRecordOption, Schema, TextFieldIndexing, TextOptions, Value, STORED, STRING,
 };
 use tantivy::tokenizer::{LowerCaser, NgramTokenizer, TextAnalyzer};
-use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, TantivyError};
+use tantivy::{Index, IndexReader, IndexWriter, ReloadPolicy, TantivyDocument, TantivyError, Term};
 use thiserror::Error;
 
 use crate::compound::Compound;
+use crate::currency::CURRENCIES_BIN_GZ;
+use crate::facts::Facts;
+use crate::numeric::Numeric;
 use crate::rational::Rational;
 
-const SOURCES_BIN_GZ: &str = "sources.bin.gz";
+/// The name of the embedded asset containing sources.
+pub(crate) const SOURCES_BIN_GZ: &str = "sources.bin.gz";
 
 /// Error that can happen during lookup.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum LookupError {
+    /// The search index failed.
     #[error("search error: {error}")]
     TantivyError {
+        /// The underlying error.
         #[source]
         #[from]
         error: TantivyError,
     },
+    /// The query could not be parsed.
     #[error("bad query: {error}")]
     QueryParserError {
+        /// The underlying error.
         #[source]
         #[from]
         error: QueryParserError,
     },
 }
 
-/// A match from the database.
-pub(crate) enum Match {
-    /// A constant was matched.
-    Constant(Constant),
+/// Characters which have a meaning in the query syntax of the search index.
+const SPECIAL: &[char] = &[
+    '+', '-', '^', '`', ':', '{', '}', '"', '\'', '[', ']', '(', ')', '!', '\\', '*',
+];
+
+/// Escape the query so that names like `côte d'ivoire` are searched for as
+/// terms.
+fn escape(query: &str) -> String {
+    let mut escaped = String::with_capacity(query.len());
+
+    for c in query.chars() {
+        if SPECIAL.contains(&c) {
+            escaped.push('\\');
+        }
+
+        escaped.push(c);
+    }
+
+    escaped
+}
+
+/// The number of candidates considered by a lookup.
+const CANDIDATES: usize = 5;
+
+/// A candidate match from the database, as returned by [Db::lookup].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Candidate {
+    /// How well the candidate matches the query. Higher is better.
+    pub score: f32,
+    /// The constant which was matched.
+    pub constant: Constant,
 }
 
 /// A single constant.
@@ -50,16 +88,103 @@ pub struct Constant {
     /// Tokens that can be queried for.
     #[serde(default)]
     pub tokens: Vec<Box<str>>,
+    /// The entity the constant is an attribute of, like `Mars` or `Finland`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<Box<str>>,
+    /// The attribute of the entity the constant is, like `mass` or
+    /// `population`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attribute: Option<Box<str>>,
     /// The description of a constant.
     pub description: Box<str>,
     /// The value of a constant.
     pub value: Rational,
     /// The unit of a constant.
     pub unit: Compound,
+    /// Whether the value of the constant is exact, like defined constants are.
+    /// Measured constants are approximate.
+    #[serde(default)]
+    pub exact: bool,
+    /// The standard uncertainty of a measured constant, in the same unit as
+    /// its value.
+    #[serde(default)]
+    pub uncertainty: Option<Rational>,
+    /// Values of a constant which changes over time, indexed by year. The
+    /// value of such a constant is its most recent one.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub series: BTreeMap<i32, Rational>,
+}
+
+impl Constant {
+    /// Get the value of the constant, including whether it is exact and its
+    /// uncertainty.
+    pub fn numeric(&self) -> Numeric {
+        Numeric::new(self.value.clone(), self.unit.clone())
+            .with_exact(self.exact)
+            .uncertain(self.uncertainty.clone())
+    }
+
+    /// Get the constant as it was in the given year, using the most recent
+    /// value in its series on or before it, or the latest value if no year is
+    /// given. The year of the value is added to the description.
+    ///
+    /// Returns `None` if the constant has no value on or before the given
+    /// year.
+    ///
+    /// ```
+    /// use anything::{Compound, Constant, Rational};
+    ///
+    /// let constant = Constant {
+    ///     source: None,
+    ///     tokens: vec!["population".into(), "finland".into()],
+    ///     entity: Some("Finland".into()),
+    ///     attribute: Some("population".into()),
+    ///     description: "Population of Finland".into(),
+    ///     value: Rational::new(5530719, 1),
+    ///     unit: Compound::empty(),
+    ///     exact: false,
+    ///     uncertainty: None,
+    ///     series: [(1990, Rational::new(4986431, 1)), (2020, Rational::new(5530719, 1))]
+    ///         .into_iter()
+    ///         .collect(),
+    /// };
+    ///
+    /// let c = constant.in_year(Some(1995)).unwrap();
+    /// assert_eq!(c.value, Rational::new(4986431, 1));
+    /// assert_eq!(&*c.description, "Population of Finland in 1990");
+    ///
+    /// let c = constant.in_year(None).unwrap();
+    /// assert_eq!(&*c.description, "Population of Finland in 2020");
+    ///
+    /// assert!(constant.in_year(Some(1980)).is_none());
+    /// ```
+    pub fn in_year(&self, year: Option<i32>) -> Option<Constant> {
+        let latest = self.series.last_key_value()?;
+
+        let (year, value) = match year {
+            Some(year) => self.series.range(..=year).next_back()?,
+            None => latest,
+        };
+
+        // NB: the uncertainty only applies to the most recent value.
+        let uncertainty = if *year == *latest.0 {
+            self.uncertainty.clone()
+        } else {
+            None
+        };
+
+        Some(Constant {
+            description: format!("{} in {year}", self.description).into(),
+            value: value.clone(),
+            uncertainty,
+            series: BTreeMap::new(),
+            ..self.clone()
+        })
+    }
 }
 
 /// A single source.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Source {
     /// The unique identifier of a source.
     pub id: u64,
@@ -70,11 +195,11 @@ pub struct Source {
 }
 
 #[derive(Debug, Default)]
-struct Sources {
+pub(crate) struct Sources {
     /// Vector of sources.
-    sources: Vec<Source>,
+    pub(crate) sources: Vec<Source>,
     /// Map of identifiers to source index.
-    map: HashMap<u64, usize>,
+    pub(crate) map: HashMap<u64, usize>,
 }
 
 impl<'de> Deserialize<'de> for Sources {
@@ -115,6 +240,8 @@ pub struct Doc {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct PartialConstant {
     pub tokens: Vec<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entity: Option<Box<str>>,
     #[serde(flatten)]
     pub content: serde_cbor::Value,
 }
@@ -126,6 +253,7 @@ pub struct Db {
     reader: IndexReader,
     field_data: Field,
     field_name: Field,
+    field_entity: Field,
 }
 
 impl Db {
@@ -135,20 +263,37 @@ impl Db {
         self.sources.sources.get(index)
     }
 
+    /// Iterate over all sources in the database.
+    pub fn sources(&self) -> impl Iterator<Item = &Source> {
+        self.sources.sources.iter()
+    }
+
     /// Only open the database in-memory.
     pub fn in_memory() -> Result<Self> {
-        Self::open_inner(true)
+        Self::open_inner(true, &Facts::default())
+    }
+
+    /// Only open the database in-memory, including the given user facts.
+    pub fn in_memory_with(facts: &Facts) -> Result<Self> {
+        Self::open_inner(true, facts)
     }
 
     /// Open the default database.
     pub fn open() -> Result<Self> {
-        Self::open_inner(false)
+        Self::open_inner(false, &Facts::default())
     }
 
-    fn open_inner(in_memory: bool) -> Result<Self> {
+    /// Open the default database, including the given user facts.
+    ///
+    /// The search index is rebuilt whenever the facts change.
+    pub fn open_with(facts: &Facts) -> Result<Self> {
+        Self::open_inner(false, facts)
+    }
+
+    fn open_inner(in_memory: bool, facts: &Facts) -> Result<Self> {
         let mut config = crate::config::open()?;
 
-        let hash = config.hash_assets();
+        let hash = config.hash_assets(facts)?;
 
         let mut rebuild = match config.meta.database_hash.as_deref() {
             Some(existing) if !in_memory => existing != hash,
@@ -176,22 +321,30 @@ impl Db {
 
         let field_name = schema.get_field("name")?;
 
+        let field_entity = schema.get_field("entity")?;
+
         let reader = index
             .reader_builder()
             .reload_policy(ReloadPolicy::Manual)
             .try_into()?;
 
-        let sources = match config.get_asset(SOURCES_BIN_GZ) {
+        let mut sources: Sources = match config.get_asset(SOURCES_BIN_GZ) {
             Some(bytes) => load_bytes(bytes.data.as_ref())?,
             None => Default::default(),
         };
 
+        for source in facts.sources() {
+            sources.map.insert(source.id, sources.sources.len());
+            sources.sources.push(source.clone());
+        }
+
         let mut db = Self {
             sources,
             index,
             reader,
             field_data,
             field_name,
+            field_entity,
         };
 
         if rebuild {
@@ -201,7 +354,7 @@ impl Db {
             writer.delete_all_documents()?;
 
             for name in config.assets() {
-                if name == SOURCES_BIN_GZ {
+                if name == SOURCES_BIN_GZ || name == CURRENCIES_BIN_GZ {
                     continue;
                 }
 
@@ -211,6 +364,10 @@ impl Db {
                 }
             }
 
+            for c in facts.constants() {
+                db.add_constant(&mut writer, &c.tokens, c.entity.as_deref(), c)?;
+            }
+
             writer.commit()?;
             db.reader.reload()?;
 
@@ -225,32 +382,143 @@ impl Db {
         Ok(db)
     }
 
-    /// Perform a lookup over the given string.
-    pub(crate) fn lookup(&self, query: &str) -> Result<Option<Match>, LookupError> {
+    /// Perform a lookup over the given string, returning the best matching
+    /// candidates ordered by descending score.
+    pub fn lookup(&self, query: &str) -> Result<Vec<Candidate>, LookupError> {
+        self.search(query, CANDIDATES)
+    }
+
+    /// Search the database for constants matching the given terms, returning
+    /// at most `limit` candidates ordered by descending score.
+    ///
+    /// ```
+    /// use anything::Db;
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Db::in_memory()?;
+    /// let candidates = db.search("radius moon", 3)?;
+    ///
+    /// assert_eq!(candidates.len(), 3);
+    /// assert_eq!(&*candidates[0].constant.description, "Radius of the satellite Moon");
+    /// # Ok(()) }
+    /// ```
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<Candidate>, LookupError> {
         let searcher = self.reader.searcher();
 
         let query_parser = QueryParser::for_index(&self.index, vec![self.field_name]);
-        let query = query_parser.parse_query(query)?;
-        let top_docs = searcher.search(&query, &TopDocs::with_limit(1))?;
+        let query = query_parser.parse_query(&escape(query))?;
+        let top_docs = searcher.search(&query, &TopDocs::with_limit(limit.max(1)))?;
+
+        let mut candidates = Vec::with_capacity(top_docs.len());
 
-        for (_score, id) in top_docs {
+        for (score, id) in top_docs {
             let doc: TantivyDocument = searcher.doc(id)?;
 
-            if let Some(value) = doc.get_first(self.field_data) {
-                let Some(bytes) = value.as_bytes() else {
-                    continue;
-                };
+            if let Some(constant) = self.decode(&doc) {
+                candidates.push(Candidate { score, constant });
+            }
+        }
+
+        Ok(candidates)
+    }
 
-                let c: Constant = match serde_cbor::from_slice(bytes) {
-                    Ok(c) => c,
-                    Err(..) => continue,
-                };
+    /// List every constant in the database which came from the given source,
+    /// in the order they were loaded.
+    pub fn list(&self, source: u64) -> Result<Vec<Constant>, LookupError> {
+        let searcher = self.reader.searcher();
+
+        let mut ids = searcher
+            .search(&AllQuery, &DocSetCollector)?
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        ids.sort();
+
+        let mut constants = Vec::new();
+
+        for id in ids {
+            let doc: TantivyDocument = searcher.doc(id)?;
 
-                return Ok(Some(Match::Constant(c)));
+            if let Some(constant) = self.decode(&doc) {
+                if constant.source == Some(source) {
+                    constants.push(constant);
+                }
             }
         }
 
-        Ok(None)
+        Ok(constants)
+    }
+
+    /// Get every attribute of the given entity, in the order they were loaded.
+    ///
+    /// Entities and attributes are matched exactly, ignoring case and treating
+    /// underscores like spaces, so `united_states` is the entity `United
+    /// States`.
+    pub fn entity(&self, entity: &str) -> Result<Vec<Constant>, LookupError> {
+        let searcher = self.reader.searcher();
+
+        let term = Term::from_field_text(self.field_entity, &normalize(entity));
+        let query = TermQuery::new(term, IndexRecordOption::Basic);
+
+        let mut ids = searcher
+            .search(&query, &DocSetCollector)?
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        ids.sort();
+
+        let mut constants = Vec::with_capacity(ids.len());
+
+        for id in ids {
+            let doc: TantivyDocument = searcher.doc(id)?;
+
+            if let Some(constant) = self.decode(&doc) {
+                constants.push(constant);
+            }
+        }
+
+        Ok(constants)
+    }
+
+    /// Get the given attribute of an entity, like the `mass` of `mars`.
+    ///
+    /// ```
+    /// use anything::{Db, Facts};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut facts = Facts::new();
+    ///
+    /// facts.load_toml(r#"
+    /// [[constants]]
+    /// entity = "Mars"
+    /// attribute = "mass"
+    /// description = "Mass of Mars"
+    /// value = "6.4171e23"
+    /// unit = "kg"
+    /// "#)?;
+    ///
+    /// let db = Db::in_memory_with(&facts)?;
+    ///
+    /// let mass = db.get("mars", "mass")?.unwrap();
+    /// assert_eq!(&*mass.description, "Mass of Mars");
+    ///
+    /// assert!(db.get("mars", "colour")?.is_none());
+    /// # Ok(()) }
+    /// ```
+    pub fn get(&self, entity: &str, attribute: &str) -> Result<Option<Constant>, LookupError> {
+        let attribute = normalize(attribute);
+
+        Ok(self.entity(entity)?.into_iter().find(|c| {
+            c.attribute
+                .as_deref()
+                .is_some_and(|a| normalize(a) == attribute)
+        }))
+    }
+
+    /// Decode the constant stored in a document.
+    fn decode(&self, doc: &TantivyDocument) -> Option<Constant> {
+        let bytes = doc.get_first(self.field_data)?.as_bytes()?;
+        serde_cbor::from_slice(bytes).ok()
     }
 
     /// Load a document from the given bytes.
@@ -258,18 +526,37 @@ impl Db {
         let doc: Doc = load_bytes(bytes)?;
 
         for c in doc.constants {
-            let mut doc = TantivyDocument::default();
+            self.add_constant(writer, &c.tokens, c.entity.as_deref(), &c)?;
+        }
 
-            let bytes = serde_cbor::to_vec(&c)?;
-            doc.add_bytes(self.field_data, &bytes);
+        Ok(())
+    }
 
-            for token in &c.tokens {
-                doc.add_text(self.field_name, token.as_ref());
-            }
+    /// Add a single constant to the index under the given tokens and entity.
+    fn add_constant<T>(
+        &self,
+        writer: &mut IndexWriter,
+        tokens: &[Box<str>],
+        entity: Option<&str>,
+        c: &T,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let mut doc = TantivyDocument::default();
 
-            writer.add_document(doc)?;
+        let bytes = serde_cbor::to_vec(c)?;
+        doc.add_bytes(self.field_data, &bytes);
+
+        for token in tokens {
+            doc.add_text(self.field_name, token.as_ref());
         }
 
+        if let Some(entity) = entity {
+            doc.add_text(self.field_entity, normalize(entity));
+        }
+
+        writer.add_document(doc)?;
         Ok(())
     }
 }
@@ -282,8 +569,11 @@ fn open_index(config: &crate::config::Config) -> Result<(bool, Index)> {
 
     if !force_rebuild {
         if let Ok(index) = Index::open_in_dir(&config.index_path) {
-            log::trace!("opened index: {}", config.index_path.display());
-            return Ok((false, index));
+            // NB: an index built with an older schema has to be rebuilt.
+            if index.schema() == build_schema() {
+                log::trace!("opened index: {}", config.index_path.display());
+                return Ok((false, index));
+            }
         }
     }
 
@@ -309,10 +599,30 @@ fn build_schema() -> Schema {
     schema.add_bytes_field("data", STORED);
 
     schema.add_text_field("name", text_options);
+    schema.add_text_field("entity", STRING);
     schema.build()
 }
 
-fn load_bytes<T>(bytes: &[u8]) -> Result<T, serde_cbor::Error>
+/// Normalize the name of an entity or attribute so that it can be matched
+/// exactly.
+pub(crate) fn normalize(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+
+    for word in name
+        .split(|c: char| c.is_whitespace() || c == '_')
+        .filter(|w| !w.is_empty())
+    {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+
+        out.push_str(&word.to_lowercase());
+    }
+
+    out
+}
+
+pub(crate) fn load_bytes<T>(bytes: &[u8]) -> Result<T, serde_cbor::Error>
 where
     for<'de> T: Deserialize<'de>,
 {
@@ -8,6 +8,7 @@ use crate::compound::Compound;
 use crate::db::LookupError;
 use crate::rational::ParseRationalError;
 use crate::syntax::parser::Syntax;
+use crate::unit_registry::UnitDefinitionError;
 
 /// A facts error.
 #[derive(Debug, Error)]
@@ -26,6 +27,11 @@ impl Error {
     pub(crate) fn new(span: Span<u32>, kind: ErrorKind) -> Self {
         Self { span, kind }
     }
+
+    /// Replace the span of the current error.
+    pub(crate) fn with_span(self, span: Span<u32>) -> Self {
+        Self { span, ..self }
+    }
 }
 
 /// En evaluation error.
@@ -43,13 +49,21 @@ pub(crate) enum ErrorKind {
     #[error("illegal operation: {lhs} {op} {rhs}")]
     IllegalOperation {
         op: &'static str,
-        lhs: Compound,
-        rhs: Compound,
+        lhs: Box<str>,
+        rhs: Box<str>,
     },
     #[error("conversion from {from} to {to} is not possible")]
     ConversionNotPossible { from: Compound, to: Compound },
     #[error("cannot cast `{from}` to `{to}`")]
     IllegalCast { from: Compound, to: Compound },
+    #[error("cannot cast an instant in time to `{to}`, only to `unix` or `utc`")]
+    InstantCast { to: Compound },
+    #[error("expected an instant in time, but got a value with unit `{unit}`")]
+    ExpectedInstant { unit: Compound },
+    #[error("instant in time is too far from 1970-01-01, it must be within a billion years")]
+    InstantOutOfRange,
+    #[error("bad date `{date}`, expected one like `2026-10-18` or `2026-10-18T09:30:00+02:00`")]
+    BadDate { date: Box<str> },
     #[error("bad decimal number: {error}")]
     ParseRationalError { error: ParseRationalError },
     #[error("bad number: {error}")]
@@ -60,6 +74,27 @@ pub(crate) enum ErrorKind {
     Expected { actual: Syntax, expected: Syntax },
     #[error("nothing matching `{query}` found in database")]
     Missing { query: Box<str> },
+    #[error("`{query}` is ambiguous, it could be: {}", .alternatives.join("; "))]
+    Ambiguous {
+        query: Box<str>,
+        alternatives: Box<[Box<str>]>,
+    },
+    #[error("`{query}` doesn't change over time, so it has no value in a given year")]
+    NotTimeSeries { query: Box<str> },
+    #[error("no value for `{query}` in or before {year}")]
+    MissingYear { query: Box<str>, year: i32 },
+    #[error("expected a year")]
+    ExpectedYear,
+    #[error("no entity named `{entity}`")]
+    MissingEntity { entity: Box<str> },
+    #[error("`{entity}` has no attribute `{attribute}`, it has: {}", .attributes.join(", "))]
+    MissingAttribute {
+        entity: Box<str>,
+        attribute: Box<str>,
+        attributes: Box<[Box<str>]>,
+    },
+    #[error("`{formula}` is not a chemical formula")]
+    BadFormula { formula: Box<str> },
     #[error("unit `{unit}` is not a valid unit")]
     IllegalUnit { unit: Box<str> },
     #[error("missing function `{name}`")]
@@ -68,6 +103,23 @@ pub(crate) enum ErrorKind {
     ArgumentMismatch { expected: usize, actual: usize },
     #[error("bad argument {argument}")]
     BadArgument { argument: usize },
+    #[error("argument {argument} must be dimensionless, but has unit `{unit}`")]
+    DimensionlessArgument { argument: usize, unit: Compound },
+    #[error("argument {argument} must be an angle, but has unit `{unit}`")]
+    AngleArgument { argument: usize, unit: Compound },
+    #[error("argument {argument} has unit `{actual}`, but expected `{expected}`")]
+    ArgumentUnitMismatch {
+        argument: usize,
+        expected: Compound,
+        actual: Compound,
+    },
+    #[error("bad unit definition: {error}")]
+    UnitDefinition {
+        #[source]
+        error: UnitDefinitionError,
+    },
+    #[error("maximum function call depth of {limit} exceeded")]
+    CallDepthExceeded { limit: usize },
     #[error("non-finite number in calculation")]
     NonFinite,
     #[error("missing expected node")]
@@ -82,8 +134,12 @@ pub(crate) enum ErrorKind {
     IllegalUnitNumber,
     #[error("the power must not have a unit")]
     IllegalPowerUnit,
-    #[error("the power of a number must be an integer")]
-    IllegalPowerNonInteger,
+    #[error("the power is too large")]
+    IllegalPowerTooLarge,
+    #[error("cannot take root {denom} of the unit `{unit}`")]
+    IllegalPowerRootUnit { unit: Compound, denom: u32 },
+    #[error("cannot take an even root of a negative number")]
+    IllegalPowerNegativeRoot,
     #[error("error when building tree")]
     TreeError {
         #[source]
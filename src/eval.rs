@@ -1,185 +1,1061 @@
-use num::bigint::Sign;
-use num::{Signed, Zero};
+use std::rc::Rc;
+
+use hashbrown::HashMap;
+use num::{Signed, ToPrimitive, Zero};
 use syntree::node::Children;
-use syntree::{FlavorDefault, Node, Span};
+use syntree::{FlavorDefault, Node, Span, Tree};
 
 use crate::compound::{Compound, CompoundError};
+use crate::currency::{Day, ExchangeRate};
+use crate::db::{self, Candidate, Constant};
 use crate::error::{Error, ErrorKind};
-use crate::numeric::Numeric;
+use crate::numeric::{Kind, Numeric};
 use crate::query::Description;
 use crate::rational::Rational;
-use crate::syntax::parser::Syntax;
+use crate::syntax::parser::{Parser, Syntax};
+use crate::unit::Unit;
 use crate::unit_parser::UnitParser;
-use crate::{db, Query};
+use crate::unit_registry::{UnitDefinitionError, UnitRegistry};
+use crate::{auto, chemistry, date, units, Query};
 
 use ErrorKind::*;
 use Syntax::*;
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
+/// The maximum depth of nested user-defined function calls.
+const MAX_CALL_DEPTH: usize = 128;
+/// The largest root which is approximated using rational arithmetic, larger
+/// roots are approximated using floating point.
+const MAX_EXACT_ROOT: u32 = 64;
+/// The number of decimal digits roots are approximated to.
+const ROOT_DIGITS: u32 = 40;
+/// The largest number of bits in the numerator or denominator of a value which
+/// is raised to a power.
+const MAX_POWER_BITS: u64 = 1 << 20;
+
 mod builtin;
 
-/// Built-in function to use.
-pub(crate) type BuiltIn = fn(Span<u32>, Vec<Numeric>) -> Result<Numeric>;
+/// Built-in function to use, which is called with the span of the call and
+/// each argument together with its span.
+pub(crate) type BuiltIn = fn(Span<u32>, Vec<(Span<u32>, Numeric)>) -> Result<Numeric>;
 
 /// Try to look up a built-in function.
 pub(crate) fn builtin(name: &str) -> Option<BuiltIn> {
     let builtin: BuiltIn = match name {
         "sin" => builtin::sin,
         "cos" => builtin::cos,
+        "tan" => builtin::tan,
+        "asin" => builtin::asin,
+        "acos" => builtin::acos,
+        "atan" => builtin::atan,
+        "atan2" => builtin::atan2,
+        "sinh" => builtin::sinh,
+        "cosh" => builtin::cosh,
+        "tanh" => builtin::tanh,
+        "exp" => builtin::exp,
+        "ln" => builtin::ln,
+        "log" => builtin::log,
+        "log10" => builtin::log10,
+        "log2" => builtin::log2,
         "round" => builtin::round,
         "floor" => builtin::floor,
         "ceil" => builtin::ceil,
+        "abs" => builtin::abs,
+        "min" => builtin::min,
+        "max" => builtin::max,
+        "hypot" => builtin::hypot,
+        "gcd" => builtin::gcd,
+        "lcm" => builtin::lcm,
+        "factorial" => builtin::factorial,
+        "sqrt" => builtin::sqrt,
+        "cbrt" => builtin::cbrt,
         _ => return None,
     };
 
     Some(builtin)
 }
 
-/// A context.
-pub struct Context {}
+/// The evaluation context.
+///
+/// This keeps track of variables which are assigned during evaluation, and can
+/// be re-used across multiple queries to build up a session.
+///
+/// The result of the last successful evaluation can be referenced as `_` or
+/// `ans`, unless a variable with that name has been assigned.
+///
+/// Functions defined like `kinetic(m: kg, v: m/s) = 0.5 * m * v * v` and
+/// units defined like `unit smoot = 1.7018 m` are also stored in the
+/// context.
+///
+/// ```
+/// use anything::{Context, Db, Options, parse, query};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let db = Db::in_memory()?;
+/// let mut ctx = Context::new();
+/// let mut descriptions = Vec::new();
+///
+/// let parsed = parse("mass = 3kg")?;
+/// let values = query(&parsed, &db, &mut ctx, Options::default(), &mut descriptions);
+/// assert_eq!(values.count(), 1);
+///
+/// assert!(ctx.get("mass").is_some());
+/// # Ok(()) }
+/// ```
+#[derive(Default)]
+pub struct Context {
+    variables: HashMap<Box<str>, Numeric>,
+    functions: HashMap<Box<str>, Rc<Function>>,
+    units: UnitRegistry,
+    currencies: HashMap<Unit, ExchangeRate>,
+    last: Option<Numeric>,
+    depth: usize,
+}
 
 impl Context {
     /// Construct a new empty context.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Get the value of the variable with the given name.
+    pub fn get(&self, name: &str) -> Option<&Numeric> {
+        if let Some(value) = self.variables.get(name) {
+            return Some(value);
+        }
+
+        match name {
+            "_" | "ans" => self.last.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Assign a value to the variable with the given name.
+    pub fn insert(&mut self, name: &str, value: Numeric) {
+        self.variables.insert(name.into(), value);
+    }
+
+    /// Access the units defined in this context.
+    pub fn units(&self) -> &UnitRegistry {
+        &self.units
+    }
+
+    /// Mutably access the units defined in this context.
+    ///
+    /// This can be used to load additional units with
+    /// [UnitRegistry::load_toml].
+    pub fn units_mut(&mut self) -> &mut UnitRegistry {
+        &mut self.units
+    }
+
+    /// Define currency units using the exchange rates of the given day.
+    ///
+    /// Each currency is defined as a unit named after its ISO 4217 code in
+    /// terms of the euro.
+    ///
+    /// ```
+    /// use anything::{Context, Rates};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let mut rates = Rates::new();
+    ///
+    /// rates.load_toml(r#"
+    /// [[days]]
+    /// date = "2024-12-31"
+    /// rates = { USD = "1.25" }
+    /// "#)?;
+    ///
+    /// let mut ctx = Context::new();
+    /// ctx.define_currencies(rates.as_of(None)?)?;
+    /// assert!(ctx.units().get("USD").is_some());
+    /// # Ok(()) }
+    /// ```
+    pub fn define_currencies(&mut self, day: &Day) -> Result<(), UnitDefinitionError> {
+        let euro = Compound::from_iter([(Unit::Euro, (1, 0))]);
+
+        for (code, rate) in &day.rates {
+            if code.as_ref() == "EUR" {
+                continue;
+            }
+
+            if rate.is_zero() {
+                return Err(UnitDefinitionError::NonPositive);
+            }
+
+            let value = Numeric::new(Rational::new(1, 1) / rate, euro.clone());
+            let unit = self.units.define(&[code], value)?;
+
+            self.currencies.insert(
+                unit,
+                ExchangeRate {
+                    code: code.clone(),
+                    rate: rate.clone(),
+                    date: day.date.clone(),
+                    source: day.source,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Test if a function with the given name has been defined.
+    pub fn has_function(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    /// Store the result of the last evaluation.
+    pub(crate) fn set_last(&mut self, value: &Numeric) {
+        self.last = Some(value.clone());
     }
 }
 
+/// A user-defined function.
+pub(crate) struct Function {
+    /// The parameters of the function and their optional units.
+    params: Vec<(Box<str>, Option<Compound>)>,
+    /// The source of the function body.
+    source: Box<str>,
+    /// The parsed function body.
+    tree: Tree<Syntax, FlavorDefault>,
+}
+
 #[derive(Default, Debug, Clone, Copy)]
 pub struct Bias {
     #[allow(unused)]
     acceleration_bias: bool,
+    /// A substance like `10 g H2O` is kept as a mass.
+    mass_bias: bool,
+    /// A substance like `2 mol NaCl` is kept as an amount of substance.
+    amount_bias: bool,
 }
 
 impl Bias {
     /// Coerce the current bias to work with acceleration bias.
     fn with_acceleration_bias(self, acceleration_bias: bool) -> Self {
-        Self { acceleration_bias }
+        Self {
+            acceleration_bias,
+            ..self
+        }
+    }
+
+    /// Coerce the current bias to keep substances as a mass or an amount if
+    /// that is what they are cast to.
+    fn with_substance_bias(self, unit: &Compound) -> Self {
+        Self {
+            mass_bias: unit.is_mass(),
+            amount_bias: unit.is_amount(),
+            ..self
+        }
     }
 }
 
-fn add(span: Span<u32>, a: Numeric, mut b: Numeric) -> Result<Numeric> {
-    match a.unit.factor(&b.unit, &mut b.value) {
-        Ok(true) => Ok(Numeric::new(a.value + b.value, a.unit)),
-        Ok(false) => Err(Error::new(
+/// Get the upper bound of an uncertain value.
+///
+/// Unit conversions can involve offsets, so the uncertainty of a value is
+/// converted by converting its upper bound along with the value, and then
+/// recovered with [recover].
+fn upper(n: &Numeric) -> Option<Rational> {
+    let uncertainty = n.uncertainty.as_ref()?;
+    Some(n.value.clone() + uncertainty.clone())
+}
+
+/// Recover the uncertainty of a converted value from its converted upper
+/// bound.
+fn recover(upper: Option<Rational>, value: &Rational) -> Option<Rational> {
+    Some((upper? - value.clone()).abs())
+}
+
+/// Combine independent uncertainties in quadrature, as the square root of the
+/// sum of their squares. Returns `None` if none of them are set.
+fn quadrature(terms: impl IntoIterator<Item = Option<Rational>>) -> Option<Rational> {
+    let sum = terms
+        .into_iter()
+        .flatten()
+        .map(|term| &term * &term)
+        .reduce(|a, b| a + b)?;
+
+    sum.root(2).or_else(|| sum.root_approx(2, ROOT_DIGITS))
+}
+
+/// Call a built-in function, propagating the uncertainty of its arguments to
+/// first order by calling it again with each uncertain argument raised by its
+/// uncertainty.
+///
+/// Functions which propagate uncertainty themselves, like `sqrt`, are left
+/// alone. If the function can't be called with a raised argument, like `gcd`
+/// with a fractional one, the result is only marked as approximate.
+fn call_builtin(
+    span: Span<u32>,
+    builtin: BuiltIn,
+    mut args: Vec<(Span<u32>, Numeric)>,
+) -> Result<Numeric> {
+    if args.iter().all(|(_, n)| n.uncertainty.is_none()) {
+        return builtin(span, args);
+    }
+
+    let result = builtin(span, args.clone())?;
+
+    if result.uncertainty.is_some() {
+        return Ok(result);
+    }
+
+    let uncertain = args
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(index, (_, n))| Some((index, n.uncertainty.take()?)))
+        .collect::<Vec<_>>();
+
+    let mut terms = Vec::with_capacity(uncertain.len());
+
+    for (index, uncertainty) in uncertain {
+        let mut raised = args.clone();
+        raised[index].1.value += uncertainty;
+
+        let Ok(mut raised) = builtin(span, raised) else {
+            return Ok(result.with_exact(false));
+        };
+
+        if !matches!(
+            result.unit.factor(&raised.unit, &mut raised.value),
+            Ok(true)
+        ) {
+            return Ok(result.with_exact(false));
+        }
+
+        terms.push(Some((raised.value - result.value.clone()).abs()).filter(|t| !t.is_zero()));
+    }
+
+    let uncertainty = quadrature(terms);
+    Ok(result.uncertain(uncertainty))
+}
+
+/// Convert the uncertainties of both operands of a multiplication or
+/// division into the units used by the result.
+fn product_uncertainties(a: &Numeric, b: &Numeric, n: i32) -> [Option<Rational>; 2] {
+    if a.uncertainty.is_none() && b.uncertainty.is_none() {
+        return [None, None];
+    }
+
+    let (mut a_value, mut b_value) = (a.value.clone(), b.value.clone());
+    let mut a_upper = upper(a).unwrap_or_else(|| a.value.clone());
+    let mut b_upper = upper(b).unwrap_or_else(|| b.value.clone());
+
+    if a.unit.mul(&b.unit, n, &mut a_value, &mut b_value).is_err()
+        || a.unit.mul(&b.unit, n, &mut a_upper, &mut b_upper).is_err()
+    {
+        return [None, None];
+    }
+
+    [
+        a.uncertainty.as_ref().map(|_| (a_upper - a_value).abs()),
+        b.uncertainty.as_ref().map(|_| (b_upper - b_value).abs()),
+    ]
+}
+
+/// Look up a constant in the database, optionally as it was in the given
+/// year.
+///
+/// A query like `mass of mars` is resolved to the attribute of an entity if
+/// there is one, otherwise the database is searched for it.
+fn lookup(q: &mut Query<'_>, span: Span<u32>, query: &str, year: Option<i32>) -> Result<Numeric> {
+    let constant = match attribute_of(q, span, query)? {
+        Some(constant) => constant,
+        None => search(q, span, query)?,
+    };
+
+    in_year(q, span, query, constant, year)
+}
+
+/// Resolve a query like `mass of mars` or `population of the world` to the
+/// attribute of an entity, if there is one.
+fn attribute_of(q: &mut Query<'_>, span: Span<u32>, query: &str) -> Result<Option<Constant>> {
+    let words = query.split_whitespace().collect::<Vec<_>>();
+
+    let Some(n) = words.iter().position(|w| w.eq_ignore_ascii_case("of")) else {
+        return Ok(None);
+    };
+
+    let attribute = &words[..n];
+
+    let entity = match &words[n + 1..] {
+        [the, rest @ ..] if the.eq_ignore_ascii_case("the") => rest,
+        rest => rest,
+    };
+
+    if attribute.is_empty() || entity.is_empty() {
+        return Ok(None);
+    }
+
+    q.db.get(&entity.join(" "), &attribute.join(" "))
+        .map_err(|error| Error::new(span, LookupError { error }))
+}
+
+/// Get the attribute of an entity, like `mars.mass`.
+fn attribute(
+    q: &mut Query<'_>,
+    span: Span<u32>,
+    entity: &str,
+    attribute: &str,
+) -> Result<Constant> {
+    let constants =
+        q.db.entity(entity)
+            .map_err(|error| Error::new(span, LookupError { error }))?;
+
+    if constants.is_empty() {
+        return Err(Error::new(
+            span,
+            MissingEntity {
+                entity: entity.into(),
+            },
+        ));
+    }
+
+    let normalized = db::normalize(attribute);
+
+    let mut attributes = Vec::with_capacity(constants.len());
+
+    for c in constants {
+        let Some(a) = c.attribute.as_deref() else {
+            continue;
+        };
+
+        if db::normalize(a) == normalized {
+            return Ok(c);
+        }
+
+        attributes.push(a.into());
+    }
+
+    Err(Error::new(
+        span,
+        MissingAttribute {
+            entity: entity.into(),
+            attribute: attribute.into(),
+            attributes: attributes.into(),
+        },
+    ))
+}
+
+/// Search the database for the constant best matching the given query,
+/// unless it is ambiguous.
+fn search(q: &mut Query<'_>, span: Span<u32>, query: &str) -> Result<Constant> {
+    let candidates =
+        q.db.lookup(query)
+            .map_err(|error| Error::new(span, LookupError { error }))?;
+
+    if q.options.candidates {
+        q.descriptions
+            .push(Description::Candidates(query.into(), candidates.clone()));
+    }
+
+    let Some((best, rest)) = candidates.split_first() else {
+        return Err(Error::new(
             span,
-            IllegalOperation {
-                op: "+",
-                lhs: a.unit,
-                rhs: b.unit,
+            Missing {
+                query: query.into(),
             },
-        )),
-        Err(CompoundError) => Err(Error::new(
+        ));
+    };
+
+    let alternatives = rest
+        .iter()
+        .filter(|c| c.score >= best.score * AMBIGUITY)
+        .collect::<Vec<_>>();
+
+    // NB: a candidate which has every word in the query as a token
+    // wins over similarly scoring ones, like `niger` over `nigeria`.
+    if !alternatives.is_empty()
+        && (!has_all_words(query, best) || alternatives.iter().any(|c| has_all_words(query, c)))
+    {
+        return Err(Error::new(
             span,
-            ConversionNotPossible {
-                from: a.unit,
-                to: b.unit,
+            Ambiguous {
+                query: query.into(),
+                alternatives: [best]
+                    .into_iter()
+                    .chain(alternatives)
+                    .map(|c| c.constant.description.clone())
+                    .collect(),
             },
-        )),
+        ));
     }
+
+    Ok(best.constant.clone())
 }
 
-fn sub(span: Span<u32>, a: Numeric, mut b: Numeric) -> Result<Numeric> {
-    match a.unit.factor(&b.unit, &mut b.value) {
-        Ok(true) => Ok(Numeric::new(a.value - b.value, a.unit)),
-        Ok(false) => Err(Error::new(
+/// Get the value of a constant which was looked up, as it was in the given
+/// year.
+fn in_year(
+    q: &mut Query<'_>,
+    span: Span<u32>,
+    query: &str,
+    constant: Constant,
+    year: Option<i32>,
+) -> Result<Numeric> {
+    let constant = match (constant.in_year(year), year) {
+        (Some(constant), _) => constant,
+        (None, None) => constant,
+        (None, Some(..)) if constant.series.is_empty() => {
+            return Err(Error::new(
+                span,
+                NotTimeSeries {
+                    query: query.into(),
+                },
+            ));
+        }
+        (None, Some(year)) => {
+            return Err(Error::new(
+                span,
+                MissingYear {
+                    query: query.into(),
+                    year,
+                },
+            ));
+        }
+    };
+
+    if q.options.describe {
+        q.descriptions
+            .push(Description::Constant(query.into(), constant.clone()));
+    }
+
+    Ok(constant.numeric())
+}
+
+/// Look up a constant with a call like `population(world, 2000)`, where the
+/// name is an attribute of the entity named by any word arguments, or
+/// otherwise together with them makes up the query. A trailing number is the
+/// year.
+fn lookup_call(
+    q: &mut Query<'_>,
+    span: Span<u32>,
+    name: &str,
+    arguments: Node<'_, Syntax, FlavorDefault>,
+) -> Result<Numeric> {
+    let args = arguments.children().skip_tokens().collect::<Vec<_>>();
+
+    let (words, year) = match args.split_last() {
+        Some((last, words)) if last.value() == NUMBER => (words, Some(last)),
+        _ => (&args[..], None),
+    };
+
+    if words.is_empty() || !words.iter().all(|n| matches!(n.value(), WORD | SENTENCE)) {
+        return Err(Error::new(span, MissingFunction { name: name.into() }));
+    }
+
+    let mut query = name.to_owned();
+    let mut entity = String::new();
+
+    for node in words {
+        if !entity.is_empty() {
+            entity.push(' ');
+        }
+
+        entity.push_str(q.source(*node.span()));
+        query.push(' ');
+        query.push_str(q.source(*node.span()));
+    }
+
+    let constant =
+        q.db.get(&entity, name)
+            .map_err(|error| Error::new(span, LookupError { error }))?;
+
+    // NB: misspelled functions shouldn't match constants by accident, so the
+    // call must match a constant exactly.
+    if constant.is_none() {
+        let candidates =
+            q.db.lookup(&query)
+                .map_err(|error| Error::new(span, LookupError { error }))?;
+
+        if !candidates.first().is_some_and(|c| has_all_words(&query, c)) {
+            return Err(Error::new(span, MissingFunction { name: name.into() }));
+        }
+    }
+
+    let year = match year {
+        Some(node) => match q.source(*node.span()).parse::<i32>() {
+            Ok(year) => Some(year),
+            Err(..) => return Err(Error::new(*node.span(), ExpectedYear)),
+        },
+        None => None,
+    };
+
+    match constant {
+        Some(constant) => in_year(q, span, &query, constant, year),
+        None => lookup(q, span, &query, year),
+    }
+}
+
+/// Candidates which score at least this fraction of the best match of a
+/// lookup are considered to be ambiguous with it.
+const AMBIGUITY: f32 = 0.9;
+
+/// Test if every word in the query is one of the tokens of the candidate.
+fn has_all_words(query: &str, c: &Candidate) -> bool {
+    query.split_whitespace().all(|word| {
+        c.constant
+            .tokens
+            .iter()
+            .any(|token| token.eq_ignore_ascii_case(word))
+    })
+}
+
+/// The attribute of elements in the database which is their atomic mass.
+const ATOMIC_MASS: &str = "atomic mass";
+
+/// The unit of molar mass, `g/mol`.
+fn grams_per_mole() -> Compound {
+    Compound::from_iter([(Unit::KiloGram, (1, -3)), (Unit::Mole, (-1, 0))])
+}
+
+/// Calculate the molar mass of a chemical formula like `H2SO4` in `g/mol`,
+/// from the atomic masses of its elements in the database.
+fn molar_mass(q: &mut Query<'_>, span: Span<u32>, formula: &str) -> Result<Numeric> {
+    let Some(elements) = chemistry::parse(formula) else {
+        return Err(Error::new(
             span,
-            IllegalOperation {
-                op: "-",
-                lhs: a.unit,
-                rhs: b.unit,
+            BadFormula {
+                formula: formula.into(),
             },
-        )),
-        Err(CompoundError) => Err(Error::new(
+        ));
+    };
+
+    let dalton = Compound::from_iter([(Unit::Derived(units::mass::DALTON), (1, 0))]);
+    let mut value = Rational::zero();
+    let mut uncertainty = None::<Rational>;
+
+    for (name, count) in elements {
+        let constant = attribute(q, span, name, ATOMIC_MASS)?;
+        let query = format!("{ATOMIC_MASS} of {name}");
+        let mass = in_year(q, span, &query, constant, None)?;
+        let mass = cast(span, mass, dalton.clone())?;
+        let count = Rational::new(count, 1u32);
+
+        // NB: atoms of the same element share the error of its atomic mass,
+        // so uncertainties are conservatively added up rather than combined
+        // in quadrature.
+        if let Some(u) = &mass.uncertainty {
+            uncertainty = Some(uncertainty.unwrap_or_else(Rational::zero) + u * &count);
+        }
+
+        value += mass.value * count;
+    }
+
+    // NB: the molar mass constant is `1 g/mol` to within parts per billion,
+    // so the atomic mass in daltons is the molar mass in `g/mol`.
+    Ok(Numeric::approximate(value, grams_per_mole()).uncertain(uncertainty))
+}
+
+/// Calculate the molar mass of the chemical formula which is the only
+/// argument of a call like `molar_mass(H2SO4)`.
+fn molar_mass_call(
+    q: &mut Query<'_>,
+    span: Span<u32>,
+    arguments: Node<'_, Syntax, FlavorDefault>,
+) -> Result<Numeric> {
+    let args = arguments.children().skip_tokens().collect::<Vec<_>>();
+
+    let [formula] = &args[..] else {
+        return Err(Error::new(
             span,
-            ConversionNotPossible {
-                from: a.unit,
-                to: b.unit,
+            ArgumentMismatch {
+                expected: 1,
+                actual: args.len(),
             },
-        )),
+        ));
+    };
+
+    molar_mass(q, *formula.span(), q.source(*formula.span()))
+}
+
+/// Convert a value whose unit ends with a chemical formula, like `10 g H2O` or
+/// `2 mol NaCl`, between the mass and the amount of that substance, unless it
+/// is cast to what it already is.
+///
+/// Returns `None` if the unit doesn't end with a formula, or if the rest of
+/// the unit isn't a mass or an amount of substance.
+fn substance(
+    q: &mut Query<'_>,
+    value: &Numeric,
+    node: Node<'_, Syntax, FlavorDefault>,
+    bias: Bias,
+) -> Result<Option<Numeric>> {
+    let mut nodes = node.children();
+
+    let Some(last) = nodes.next_back().filter(|n| n.value() == WORD) else {
+        return Ok(None);
+    };
+
+    let formula = q.source(*last.span());
+
+    // NB: a word which is a single unit, like the kelvin in `1 mol K`, is never
+    // taken to be a formula.
+    if chemistry::parse(formula).is_none() || is_unit(formula, &q.ctx.units) {
+        return Ok(None);
+    }
+
+    let Ok(unit) = unit(q.source_as_str(), &q.ctx.units, nodes, bias) else {
+        return Ok(None);
+    };
+
+    if unit.is_empty() {
+        return Ok(None);
+    }
+
+    let span = *node.span();
+    let grams = Compound::from_iter([(Unit::KiloGram, (1, -3))]);
+    let moles = Compound::from_iter([(Unit::Mole, (1, 0))]);
+
+    let value = Numeric::new(value.value.clone(), unit)
+        .with_exact(value.exact)
+        .uncertain(value.uncertainty.clone());
+
+    if let Ok(mass) = cast(span, value.clone(), grams.clone()) {
+        if bias.mass_bias {
+            return Ok(Some(mass));
+        }
+
+        let molar = molar_mass(q, *last.span(), formula)?;
+        return Ok(Some(cast(span, div(span, mass, molar)?, moles)?));
+    }
+
+    if let Ok(amount) = cast(span, value, moles) {
+        if bias.amount_bias {
+            return Ok(Some(amount));
+        }
+
+        let molar = molar_mass(q, *last.span(), formula)?;
+        return Ok(Some(cast(span, mul(span, amount, molar)?, grams)?));
+    }
+
+    Ok(None)
+}
+
+/// Test if the given word is a single unit, like `K` or `mol`, rather than a
+/// run of units like `NaCl`.
+fn is_unit(word: &str, registry: &UnitRegistry) -> bool {
+    let mut parser = UnitParser::new(word, registry);
+    matches!((parser.next(), parser.next()), (Ok(Some(_)), Ok(None)))
+}
+
+/// A cast to a keyword like `to auto` or `to unix`, rather than to a unit.
+type Keyword = fn(Span<u32>, Numeric) -> Result<Numeric>;
+
+/// Construct an error for an operation which isn't supported by the given
+/// values.
+fn illegal(span: Span<u32>, op: &'static str, a: &Numeric, b: &Numeric) -> Error {
+    Error::new(
+        span,
+        IllegalOperation {
+            op,
+            lhs: operand(a),
+            rhs: operand(b),
+        },
+    )
+}
+
+/// Describe an operand in an illegal operation.
+fn operand(n: &Numeric) -> Box<str> {
+    if n.is_instant() {
+        "instant".into()
+    } else if n.unit.is_empty() {
+        "a number".into()
+    } else {
+        n.unit.to_string().into()
+    }
+}
+
+/// Construct an error for two units which couldn't be combined.
+fn compound_error(span: Span<u32>, error: CompoundError, from: Compound, to: Compound) -> Error {
+    match error {
+        CompoundError::Conversion => Error::new(span, ConversionNotPossible { from, to }),
+        CompoundError::PowerTooLarge => Error::new(span, IllegalPowerTooLarge),
+    }
+}
+
+fn add(span: Span<u32>, a: Numeric, mut b: Numeric) -> Result<Numeric> {
+    // NB: instants can only be offset by durations.
+    let kind = match (a.kind, b.kind) {
+        (Kind::Quantity, Kind::Quantity) => Kind::Quantity,
+        (Kind::Instant { .. }, Kind::Quantity) if !b.unit.is_empty() => a.kind,
+        (Kind::Quantity, Kind::Instant { .. }) if !a.unit.is_empty() => return add(span, b, a),
+        _ => return Err(illegal(span, "+", &a, &b)),
+    };
+
+    let mut b_upper = upper(&b);
+
+    match a.unit.factor(&b.unit, &mut b.value) {
+        Ok(true) => {
+            if let Some(b_upper) = &mut b_upper {
+                let _ = a.unit.factor(&b.unit, b_upper);
+            }
+
+            let uncertainty = quadrature([a.uncertainty, recover(b_upper, &b.value)]);
+            let exact = a.exact && b.exact && a.unit.pi_power() == b.unit.pi_power();
+
+            let value = a.value + b.value;
+
+            if matches!(kind, Kind::Instant { .. }) && !date::in_range(&value) {
+                return Err(Error::new(span, InstantOutOfRange));
+            }
+
+            Ok(Numeric::new(value, a.unit)
+                .with_exact(exact)
+                .uncertain(uncertainty)
+                .with_kind(kind))
+        }
+        Ok(false) => Err(illegal(span, "+", &a, &b)),
+        Err(error) => Err(compound_error(span, error, a.unit, b.unit)),
+    }
+}
+
+fn sub(span: Span<u32>, a: Numeric, mut b: Numeric) -> Result<Numeric> {
+    // NB: the difference between two instants is a duration.
+    let kind = match (a.kind, b.kind) {
+        (Kind::Quantity, Kind::Quantity) | (Kind::Instant { .. }, Kind::Instant { .. }) => {
+            Kind::Quantity
+        }
+        (Kind::Instant { .. }, Kind::Quantity) if !b.unit.is_empty() => a.kind,
+        _ => return Err(illegal(span, "-", &a, &b)),
+    };
+
+    let mut b_upper = upper(&b);
+
+    match a.unit.factor(&b.unit, &mut b.value) {
+        Ok(true) => {
+            if let Some(b_upper) = &mut b_upper {
+                let _ = a.unit.factor(&b.unit, b_upper);
+            }
+
+            let uncertainty = quadrature([a.uncertainty, recover(b_upper, &b.value)]);
+            let exact = a.exact && b.exact && a.unit.pi_power() == b.unit.pi_power();
+
+            let value = a.value - b.value;
+
+            if matches!(kind, Kind::Instant { .. }) && !date::in_range(&value) {
+                return Err(Error::new(span, InstantOutOfRange));
+            }
+
+            Ok(Numeric::new(value, a.unit)
+                .with_exact(exact)
+                .uncertain(uncertainty)
+                .with_kind(kind))
+        }
+        Ok(false) => Err(illegal(span, "-", &a, &b)),
+        Err(error) => Err(compound_error(span, error, a.unit, b.unit)),
     }
 }
 
 fn div(span: Span<u32>, mut a: Numeric, mut b: Numeric) -> Result<Numeric> {
+    if a.is_instant() || b.is_instant() {
+        return Err(illegal(span, "/", &a, &b));
+    }
+
+    let [a_uncertainty, b_uncertainty] = product_uncertainties(&a, &b, -1);
+
     let unit = match a.unit.mul(&b.unit, -1, &mut a.value, &mut b.value) {
         Ok(unit) => unit,
-        Err(CompoundError) => {
-            return Err(Error::new(
-                span,
-                ConversionNotPossible {
-                    from: a.unit,
-                    to: b.unit,
-                },
-            ))
-        }
+        Err(error) => return Err(compound_error(span, error, a.unit, b.unit)),
     };
 
     if a.value.denom().is_zero() || b.value.numer().is_zero() {
         return Err(Error::new(span, DivideByZero));
     }
 
-    Ok(Numeric::new(a.value / b.value, unit))
+    let exact = a.exact && b.exact && a.unit.pi_power() - b.unit.pi_power() == unit.pi_power();
+    let value = a.value / &b.value;
+
+    // NB: for `a / b` the uncertainty is `sqrt((σa / b)² + (a / b * σb / b)²)`.
+    let b_abs = b.value.abs();
+    let uncertainty = quadrature([
+        a_uncertainty.map(|u| u / &b_abs),
+        b_uncertainty.map(|u| u * &value.abs() / &b_abs),
+    ]);
+
+    Ok(Numeric::new(value, unit)
+        .with_exact(exact)
+        .uncertain(uncertainty))
 }
 
 fn mul(span: Span<u32>, mut a: Numeric, mut b: Numeric) -> Result<Numeric> {
+    if a.is_instant() || b.is_instant() {
+        return Err(illegal(span, "*", &a, &b));
+    }
+
+    let [a_uncertainty, b_uncertainty] = product_uncertainties(&a, &b, 1);
+
     let unit = match a.unit.mul(&b.unit, 1, &mut a.value, &mut b.value) {
         Ok(unit) => unit,
-        Err(CompoundError) => {
-            return Err(Error::new(
-                span,
-                ConversionNotPossible {
-                    from: a.unit,
-                    to: b.unit,
-                },
-            ))
-        }
+        Err(error) => return Err(compound_error(span, error, a.unit, b.unit)),
     };
 
     if a.value.denom().is_zero() || b.value.denom().is_zero() {
         return Err(Error::new(span, DivideByZero));
     }
 
-    Ok(Numeric::new(a.value * b.value, unit))
+    let exact = a.exact && b.exact && a.unit.pi_power() + b.unit.pi_power() == unit.pi_power();
+
+    // NB: for `a * b` the uncertainty is `sqrt((b * σa)² + (a * σb)²)`.
+    let uncertainty = quadrature([
+        a_uncertainty.map(|u| u * &b.value.abs()),
+        b_uncertainty.map(|u| u * &a.value.abs()),
+    ]);
+
+    Ok(Numeric::new(a.value * b.value, unit)
+        .with_exact(exact)
+        .uncertain(uncertainty))
 }
 
+/// Raise a number to a rational power, propagating the uncertainty of both
+/// the base and the exponent.
 fn pow(span: Span<u32>, base: Numeric, pow: Numeric) -> Result<Numeric> {
+    if base.is_instant() || pow.is_instant() {
+        return Err(illegal(span, "^", &base, &pow));
+    }
+
+    if base.uncertainty.is_none() && pow.uncertainty.is_none() {
+        return pow_exact(span, base, pow);
+    }
+
+    // NB: the relative uncertainty of `a^p` is `|p| * σa / |a|` which doesn't
+    // depend on the unit of `a`, and the uncertainty coming from the exponent
+    // is `|a^p * ln(a)| * σp`.
+    let relative = match &base.uncertainty {
+        Some(u) if !base.value.is_zero() => Some(u / &base.value.abs() * &pow.value.abs()),
+        _ => None,
+    };
+
+    let ln = base.value.to_f64().map(|value| value.abs().ln());
+    let exponent_uncertainty = pow.uncertainty.as_ref().and_then(|u| u.to_f64());
+
+    let result = pow_exact(span, base, pow)?;
+    let value = result.value.abs();
+
+    let from_exponent = match (ln, exponent_uncertainty, value.to_f64()) {
+        (Some(ln), Some(u), Some(value)) => Rational::from_f64((value * ln * u).abs()),
+        _ => None,
+    };
+
+    let uncertainty = quadrature([relative.map(|r| r * &value), from_exponent]);
+    Ok(result.uncertain(uncertainty))
+}
+
+/// Raise a number to a rational power.
+///
+/// Integer powers are calculated exactly, and so are roots of perfect powers
+/// like `(9 m^2)^(1/2)`. Other roots are approximated.
+fn pow_exact(span: Span<u32>, base: Numeric, pow: Numeric) -> Result<Numeric> {
     if !pow.unit.is_empty() {
         return Err(Error::new(span, IllegalPowerUnit));
     }
 
-    if !pow.value.is_integer() {
-        return Err(Error::new(span, IllegalPowerNonInteger));
+    let (Some(numer), Some(denom)) = (pow.value.numer().to_i32(), pow.value.denom().to_u32())
+    else {
+        return Err(Error::new(span, IllegalPowerTooLarge));
+    };
+
+    let Numeric {
+        mut value,
+        unit,
+        mut exact,
+        ..
+    } = base;
+
+    let unit = if denom == 1 {
+        unit.powi(numer)
+    } else {
+        let root = match unit.root(denom) {
+            Some(root) => root,
+            None => {
+                // Try again in base units, which allows for things like
+                // `sqrt(1 hectare)`.
+                let base = unit.base();
+
+                let root = match base.factor(&unit, &mut value) {
+                    Ok(true) => base.root(denom),
+                    _ => None,
+                };
+
+                exact &= unit.pi_power() == 0;
+
+                match root {
+                    Some(root) => root,
+                    None => return Err(Error::new(span, IllegalPowerRootUnit { unit, denom })),
+                }
+            }
+        };
+
+        root.powi(numer)
+    };
+
+    let unit = unit.ok_or_else(|| Error::new(span, IllegalPowerTooLarge))?;
+
+    if value.is_zero() {
+        return match numer.signum() {
+            -1 => Err(Error::new(span, DivideByZero)),
+            0 => Ok(Numeric::new(Rational::new(1, 1), unit)),
+            _ => Ok(Numeric::new(value, unit).with_exact(exact)),
+        };
+    }
+
+    if denom > MAX_EXACT_ROOT {
+        // NB: roots of perfect powers are cheap to find even for large
+        // denominators, like `1^(1/100)`.
+        if let Some(root) = value.root(denom) {
+            let root = checked_pow(span, &root, numer)?;
+            return Ok(Numeric::new(root, unit).with_exact(exact));
+        }
+
+        if denom.is_multiple_of(2) && value.numer().is_negative() {
+            return Err(Error::new(span, IllegalPowerNegativeRoot));
+        }
+
+        let value = value
+            .to_f64()
+            .map(|value| value.powf(f64::from(numer) / f64::from(denom)))
+            .and_then(Rational::from_f64)
+            .ok_or_else(|| Error::new(span, NonFinite))?;
+
+        return Ok(Numeric::approximate(value, unit));
     }
 
-    if pow.value.is_zero() {
-        return Ok(Numeric::new(Rational::new(1, 1), base.unit));
+    let value = checked_pow(span, &value, numer)?;
+
+    if denom == 1 {
+        return Ok(Numeric::new(value, unit).with_exact(exact));
     }
 
-    if base.value.is_zero() {
-        return Ok(Numeric::new(base.value, base.unit));
+    if let Some(value) = value.root(denom) {
+        return Ok(Numeric::new(value, unit).with_exact(exact));
     }
 
-    let mut value = Rational::new(1, 1);
-    let mut pow = pow.value.numer().clone();
-    let sign = pow.signum();
+    match value.root_approx(denom, ROOT_DIGITS) {
+        Some(value) => Ok(Numeric::approximate(value, unit)),
+        None => Err(Error::new(span, IllegalPowerNegativeRoot)),
+    }
+}
 
-    let b = match sign.sign() {
-        Sign::Minus => base.value.recip(),
-        _ => base.value,
-    };
+/// Raise a value to an integer power, unless the result would have more than
+/// [MAX_POWER_BITS] bits.
+fn checked_pow(span: Span<u32>, value: &Rational, n: i32) -> Result<Rational> {
+    // NB: this is a lower bound, so that powers of `1` and `-1` are always
+    // allowed.
+    let bits = value.numer().bits().max(value.denom().bits()) - 1;
 
-    while !pow.is_zero() {
-        value *= &b;
-        pow -= &sign;
+    if bits.saturating_mul(u64::from(n.unsigned_abs())) > MAX_POWER_BITS {
+        return Err(Error::new(span, IllegalPowerTooLarge));
     }
 
-    Ok(Numeric::new(value, base.unit))
+    // NB: this uses exponentiation by squaring.
+    Ok(value.pow(n))
 }
 
 /// Parse a unit.
 pub(crate) fn unit(
     source: &str,
+    registry: &UnitRegistry,
     mut nodes: Children<'_, Syntax, FlavorDefault>,
     _bias: Bias,
 ) -> Result<Compound> {
@@ -201,7 +1077,7 @@ pub(crate) fn unit(
             }
             WORD => {
                 let unit = &source[node.range()];
-                let mut parser = UnitParser::new(unit);
+                let mut parser = UnitParser::new(unit, registry);
 
                 while let Some(result) = parser.next().transpose() {
                     let (prefix, name) = match result {
@@ -214,7 +1090,7 @@ pub(crate) fn unit(
                         }
                     };
 
-                    if let Err(expected) = compound.update(name, current, prefix) {
+                    if let Err(expected) = compound.update(name.clone(), current, prefix) {
                         return Err(Error::new(
                             *node.span(),
                             PrefixMismatch {
@@ -260,6 +1136,335 @@ pub(crate) fn unit(
     Ok(compound)
 }
 
+/// Convert a value into the given unit.
+fn cast(span: Span<u32>, mut lhs: Numeric, rhs: Compound) -> Result<Numeric> {
+    if lhs.is_instant() {
+        return Err(Error::new(span, InstantCast { to: rhs }));
+    }
+
+    let mut lhs_upper = upper(&lhs);
+
+    match rhs.factor(&lhs.unit, &mut lhs.value) {
+        Ok(true) => {
+            if let Some(lhs_upper) = &mut lhs_upper {
+                let _ = rhs.factor(&lhs.unit, lhs_upper);
+            }
+        }
+        Ok(false) => {
+            return Err(Error::new(
+                span,
+                IllegalCast {
+                    from: lhs.unit,
+                    to: rhs,
+                },
+            ));
+        }
+        Err(error) => return Err(compound_error(span, error, lhs.unit, rhs)),
+    }
+
+    let exact = lhs.exact && lhs.unit.pi_power() == rhs.pi_power();
+    let uncertainty = recover(lhs_upper, &lhs.value);
+
+    Ok(Numeric::new(lhs.value, rhs)
+        .with_exact(exact)
+        .uncertain(uncertainty))
+}
+
+/// Convert a value into its most readable unit, see [auto::best].
+pub(crate) fn auto(span: Span<u32>, value: Numeric, explicit: bool) -> Result<Numeric> {
+    match auto::best(&value, explicit) {
+        Some(unit) => cast(span, value, unit),
+        None => Ok(value),
+    }
+}
+
+/// Convert an instant in time into the number of seconds since the Unix
+/// epoch.
+fn unix(span: Span<u32>, value: Numeric) -> Result<Numeric> {
+    if !value.is_instant() {
+        return Err(Error::new(span, ExpectedInstant { unit: value.unit }));
+    }
+
+    Ok(Numeric::new(value.value, Compound::empty())
+        .with_exact(value.exact)
+        .uncertain(value.uncertainty))
+}
+
+/// Display an instant in time in UTC.
+fn utc(span: Span<u32>, value: Numeric) -> Result<Numeric> {
+    if !value.is_instant() {
+        return Err(Error::new(span, ExpectedInstant { unit: value.unit }));
+    }
+
+    Ok(value.with_kind(Kind::Instant { offset: 0 }))
+}
+
+/// Parse the units of a cast, which might be several units to break the value
+/// down into like `ft+in`, or `hms` for hours, minutes and seconds.
+///
+/// All units are checked to be compatible with the last one.
+fn cast_units(
+    q: &mut Query<'_>,
+    node: Node<'_, Syntax, FlavorDefault>,
+    bias: Bias,
+) -> Result<Vec<Compound>> {
+    let units = match node.value() {
+        MIXED_UNIT => node
+            .children()
+            .filter(|n| n.value() == UNIT)
+            .map(|n| unit(q.source_as_str(), &q.ctx.units, n.children(), bias))
+            .collect::<Result<Vec<_>>>()?,
+        _ if q.source(*node.span()) == "hms" => [
+            Unit::Derived(units::time::HOUR),
+            Unit::Derived(units::time::MINUTE),
+            Unit::Second,
+        ]
+        .into_iter()
+        .map(|unit| Compound::from_iter([(unit, (1, 0))]))
+        .collect(),
+        _ => vec![unit(
+            q.source_as_str(),
+            &q.ctx.units,
+            node.children(),
+            bias,
+        )?],
+    };
+
+    if let Some((last, rest)) = units.split_last() {
+        for unit in rest {
+            if !matches!(unit.factor(last, &mut Rational::new(1, 1)), Ok(true)) {
+                return Err(Error::new(
+                    *node.span(),
+                    IllegalCast {
+                        from: unit.clone(),
+                        to: last.clone(),
+                    },
+                ));
+            }
+        }
+    }
+
+    Ok(units)
+}
+
+/// Describe the exchange rates of any currencies used in the given unit.
+fn describe_unit(q: &mut Query<'_>, unit: &Compound) {
+    if !q.options.describe {
+        return;
+    }
+
+    for u in unit.units() {
+        let Some(rate) = q.ctx.currencies.get(&u) else {
+            continue;
+        };
+
+        let described = q.descriptions.iter().any(|d| match d {
+            Description::ExchangeRate(existing) => existing.code == rate.code,
+            _ => false,
+        });
+
+        if !described {
+            q.descriptions.push(Description::ExchangeRate(rate.clone()));
+        }
+    }
+}
+
+/// Define a function from the given `FN_DEF` node.
+pub(crate) fn define(q: &mut Query<'_>, node: Node<'_, Syntax, FlavorDefault>) -> Result<()> {
+    let mut name = None;
+    let mut params = Vec::new();
+    let mut body = None;
+
+    for child in node.children().skip_tokens() {
+        match child.value() {
+            FN_NAME => {
+                name = Some(q.source(*child.span()));
+            }
+            FN_PARAMS => {
+                for param in child.children().skip_tokens() {
+                    let mut it = param.children().skip_tokens();
+
+                    let param_name = match it.next() {
+                        Some(name) if name.value() == WORD => q.source(*name.span()),
+                        _ => return Err(Error::new(*param.span(), Unexpected { kind: WORD })),
+                    };
+
+                    let param_unit = match it.next() {
+                        Some(node) if node.value() == UNIT => Some(unit(
+                            q.source_as_str(),
+                            &q.ctx.units,
+                            node.children(),
+                            Bias::default(),
+                        )?),
+                        Some(node) => {
+                            return Err(Error::new(
+                                *node.span(),
+                                Expected {
+                                    expected: UNIT,
+                                    actual: node.value(),
+                                },
+                            ))
+                        }
+                        None => None,
+                    };
+
+                    params.push((param_name.into(), param_unit));
+                }
+            }
+            _ => {
+                body = Some(child);
+            }
+        }
+    }
+
+    let (Some(name), Some(body)) = (name, body) else {
+        return Err(Error::new(*node.span(), MissingNode));
+    };
+
+    let source: Box<str> = q.source(*body.span()).into();
+
+    let tree = match Parser::new(&source).parse_root() {
+        Ok(tree) => tree,
+        Err(error) => return Err(Error::new(*body.span(), TreeError { error })),
+    };
+
+    let function = Function {
+        params,
+        source,
+        tree,
+    };
+
+    q.ctx.functions.insert(name.into(), Rc::new(function));
+    Ok(())
+}
+
+/// Define a unit from the given `UNIT_DEF` node.
+pub(crate) fn define_unit(q: &mut Query<'_>, node: Node<'_, Syntax, FlavorDefault>) -> Result<()> {
+    let mut it = node.children().skip_tokens();
+
+    let name = match it.next() {
+        Some(name) if name.value() == WORD => q.source(*name.span()),
+        _ => return Err(Error::new(*node.span(), Unexpected { kind: WORD })),
+    };
+
+    let value = match it.next() {
+        Some(value) => eval(q, value, Bias::default())?,
+        None => return Err(Error::new(*node.span(), MissingNode)),
+    };
+
+    if let Err(error) = q.ctx.units.define(&[name], value) {
+        return Err(Error::new(*node.span(), UnitDefinition { error }));
+    }
+
+    Ok(())
+}
+
+/// Call a user-defined function.
+///
+/// Any errors raised while evaluating the function body are reported at the
+/// call site, since the body has a different source.
+fn call(
+    q: &mut Query<'_>,
+    span: Span<u32>,
+    function: &Function,
+    args: Vec<(Span<u32>, Numeric)>,
+    bias: Bias,
+) -> Result<Numeric> {
+    if args.len() != function.params.len() {
+        return Err(Error::new(
+            span,
+            ArgumentMismatch {
+                expected: function.params.len(),
+                actual: args.len(),
+            },
+        ));
+    }
+
+    if q.ctx.depth >= MAX_CALL_DEPTH {
+        return Err(Error::new(
+            span,
+            CallDepthExceeded {
+                limit: MAX_CALL_DEPTH,
+            },
+        ));
+    }
+
+    let mut bound = Vec::with_capacity(args.len());
+
+    for (argument, ((name, expected), (arg_span, mut arg))) in
+        function.params.iter().zip(args).enumerate()
+    {
+        if let Some(expected) = expected {
+            let mut arg_upper = upper(&arg);
+
+            if !matches!(expected.factor(&arg.unit, &mut arg.value), Ok(true)) {
+                return Err(Error::new(
+                    arg_span,
+                    ArgumentUnitMismatch {
+                        argument,
+                        expected: expected.clone(),
+                        actual: arg.unit,
+                    },
+                ));
+            }
+
+            if let Some(arg_upper) = &mut arg_upper {
+                let _ = expected.factor(&arg.unit, arg_upper);
+            }
+
+            let exact = arg.exact && arg.unit.pi_power() == expected.pi_power();
+            let uncertainty = recover(arg_upper, &arg.value);
+
+            arg = Numeric::new(arg.value, expected.clone())
+                .with_exact(exact)
+                .uncertain(uncertainty)
+                .with_kind(arg.kind);
+        }
+
+        bound.push((name.clone(), arg));
+    }
+
+    let mut shadowed = Vec::with_capacity(bound.len());
+
+    for (name, value) in bound {
+        let old = q.ctx.variables.insert(name.clone(), value);
+        shadowed.push((name, old));
+    }
+
+    q.ctx.depth += 1;
+
+    let result = {
+        let mut inner = Query {
+            ctx: &mut *q.ctx,
+            source: &function.source,
+            db: q.db,
+            children: function.tree.children(),
+            options: q.options,
+            descriptions: &mut *q.descriptions,
+        };
+
+        match inner.children.next_node() {
+            Some(node) => eval(&mut inner, node, bias),
+            None => Err(Error::new(span, MissingNode)),
+        }
+    };
+
+    q.ctx.depth -= 1;
+
+    for (name, old) in shadowed.into_iter().rev() {
+        match old {
+            Some(old) => {
+                q.ctx.variables.insert(name, old);
+            }
+            None => {
+                q.ctx.variables.remove(&name);
+            }
+        }
+    }
+
+    result.map_err(|error| error.with_span(span))
+}
+
 /// Helper to delay evaluation of a syntax node so that we can modify its bias.
 enum DelayedEval<'a> {
     Node(Node<'a, Syntax, FlavorDefault>),
@@ -300,34 +1505,41 @@ pub fn eval(
                     OP_MUL | OP_IMPLICIT_MUL => mul,
                     OP_POWER => pow,
                     OP_CAST => {
-                        let rhs = unit(q.source_as_str(), rhs.children(), bias)?;
-
-                        let mut lhs =
-                            base.eval(q, bias.with_acceleration_bias(rhs.is_acceleration()))?;
-
-                        match rhs.factor(&lhs.unit, &mut lhs.value) {
-                            Ok(true) => {}
-                            Ok(false) => {
-                                return Err(Error::new(
-                                    *node.span(),
-                                    IllegalCast {
-                                        from: lhs.unit,
-                                        to: rhs,
-                                    },
-                                ));
-                            }
-                            Err(CompoundError) => {
-                                return Err(Error::new(
-                                    *node.span(),
-                                    ConversionNotPossible {
-                                        from: lhs.unit,
-                                        to: rhs,
-                                    },
-                                ))
-                            }
+                        let keyword: Option<Keyword> = match q.source(*rhs.span()) {
+                            "auto" => Some(|span, value| auto(span, value, true)),
+                            "unix" => Some(unix),
+                            "utc" => Some(utc),
+                            _ => None,
+                        };
+
+                        if let Some(keyword) = keyword {
+                            let lhs = base.eval(q, bias)?;
+                            base = DelayedEval::Numeric(keyword(*node.span(), lhs)?);
+                            continue;
+                        }
+
+                        let mixed = cast_units(q, rhs, bias)?;
+
+                        let Some(rhs) = mixed.last().cloned() else {
+                            return Err(Error::new(*rhs.span(), MissingNode));
+                        };
+
+                        for unit in &mixed {
+                            describe_unit(q, unit);
                         }
 
-                        base = DelayedEval::Numeric(Numeric::new(lhs.value, rhs));
+                        let bias = bias
+                            .with_acceleration_bias(rhs.is_acceleration())
+                            .with_substance_bias(&rhs);
+                        let lhs = base.eval(q, bias)?;
+
+                        let mut value = cast(*node.span(), lhs, rhs)?;
+
+                        if mixed.len() > 1 {
+                            value = value.with_mixed(mixed);
+                        }
+
+                        base = DelayedEval::Numeric(value);
                         continue;
                     }
                     ERROR => return Err(Error::new(*op.span(), SyntaxError)),
@@ -376,31 +1588,141 @@ pub fn eval(
             };
 
             let value = eval(q, value_node, bias)?;
-            let unit = unit(q.source_as_str(), unit_node.children(), bias)?;
-            Ok(Numeric::new(value.value, unit))
+
+            if let Some(value) = substance(q, &value, unit_node, bias)? {
+                return Ok(value);
+            }
+
+            let unit = unit(q.source_as_str(), &q.ctx.units, unit_node.children(), bias)?;
+            describe_unit(q, &unit);
+            Ok(Numeric::new(value.value, unit)
+                .with_exact(value.exact)
+                .uncertain(value.uncertainty))
+        }
+        DATE => {
+            let source = q.source(*node.span());
+
+            let Some((value, offset)) = date::parse(source) else {
+                return Err(Error::new(
+                    *node.span(),
+                    BadDate {
+                        date: source.into(),
+                    },
+                ));
+            };
+
+            Ok(Numeric::instant(value, offset))
+        }
+        MIXED => {
+            let mut parts = node
+                .children()
+                .filter(|n| n.value() == WITH_UNIT)
+                .map(|n| eval(q, n, bias))
+                .collect::<Result<Vec<_>>>()?;
+
+            // NB: the sign of the first part applies to the whole value, so
+            // that `-5ft 11in` is the negation of `5ft 11in`.
+            if let [first, rest @ ..] = &mut parts[..] {
+                if first.value.numer().is_negative() {
+                    for part in rest {
+                        part.value = Rational::new(0, 1) - part.value.clone();
+                    }
+                }
+            }
+
+            // NB: parts are added to the last unit, so that `5ft 11in` is
+            // expressed exactly in inches.
+            let Some(mut value) = parts.pop() else {
+                return Err(Error::new(*node.span(), MissingNode));
+            };
+
+            while let Some(part) = parts.pop() {
+                value = add(*node.span(), value, part)?;
+            }
+
+            Ok(value)
+        }
+        UNCERTAIN => {
+            let mut numbers = node.children().filter(|n| n.value() == NUMBER);
+
+            let (Some(value), Some(uncertainty)) = (numbers.next(), numbers.next()) else {
+                return Err(Error::new(*node.span(), MissingNode));
+            };
+
+            let parse = |node: Node<'_, Syntax, FlavorDefault>| {
+                str::parse::<Rational>(q.source(*node.span()))
+                    .map_err(|error| Error::new(*node.span(), ParseRationalError { error }))
+            };
+
+            let value = parse(value)?;
+            let mut uncertainty = parse(uncertainty)?;
+
+            if node.children().any(|n| n.value() == PERCENTAGE) {
+                uncertainty = uncertainty * &value / Rational::new(100, 1);
+            }
+
+            Ok(Numeric::new(value, Compound::empty()).with_uncertainty(uncertainty))
+        }
+        ASSIGN => {
+            let mut it = node.children().skip_tokens();
+
+            let name = match it.next() {
+                Some(name) if name.value() == WORD => name,
+                _ => return Err(Error::new(*node.span(), Unexpected { kind: WORD })),
+            };
+
+            let value = match it.next() {
+                Some(value) => value,
+                None => return Err(Error::new(*node.span(), MissingNode)),
+            };
+
+            let value = eval(q, value, bias)?;
+            q.ctx.insert(q.source(*name.span()), value.clone());
+            Ok(value)
         }
         SENTENCE | WORD => {
             let s = q.source(*node.span());
 
-            let m = match q
-                .db
-                .lookup(s)
-                .map_err(|error| Error::new(*node.span(), LookupError { error }))?
-            {
-                Some(m) => m,
-                None => return Err(Error::new(*node.span(), Missing { query: s.into() })),
-            };
+            if let Some(value) = q.ctx.get(s) {
+                return Ok(value.clone());
+            }
 
-            match m {
-                db::Match::Constant(c) => {
-                    if q.options.describe {
-                        q.descriptions
-                            .push(Description::Constant(s.into(), c.clone()));
-                    }
+            match s {
+                "now" => return Ok(Numeric::instant(date::now(), 0)),
+                "today" => return Ok(Numeric::instant(date::today(), 0)),
+                _ => {}
+            }
 
-                    Ok(Numeric::new(c.value.clone(), c.unit))
+            // NB: a trailing `in <year>` picks a value of a constant which
+            // changes over time, like `population finland in 1990`.
+            if node.value() == SENTENCE {
+                let words = node.children().skip_tokens().collect::<Vec<_>>();
+
+                if let [.., last_word, in_word, year] = &words[..] {
+                    if let ("in", Ok(year)) = (
+                        q.source(*in_word.span()),
+                        q.source(*year.span()).parse::<i32>(),
+                    ) {
+                        let span = Span::new(node.span().start, last_word.span().end);
+                        let query = q.source(span);
+                        return lookup(q, span, query, Some(year));
+                    }
                 }
             }
+
+            lookup(q, *node.span(), s, None)
+        }
+        ATTRIBUTE => {
+            let mut it = node.children().skip_tokens();
+
+            let (Some(entity), Some(name)) = (it.next(), it.next()) else {
+                return Err(Error::new(*node.span(), MissingNode));
+            };
+
+            let entity = q.source(*entity.span());
+            let name = q.source(*name.span());
+            let constant = attribute(q, *node.span(), entity, name)?;
+            in_year(q, *node.span(), q.source(*node.span()), constant, None)
         }
         PERCENTAGE => {
             let number = match node.first() {
@@ -426,21 +1748,37 @@ pub fn eval(
                 _ => return Err(Error::new(*node.span(), Unexpected { kind: FN_NAME })),
             };
 
-            let arguments = match it.next() {
-                Some(arguments) if arguments.value() == FN_ARGUMENTS => arguments,
-                _ => return Err(Error::new(*node.span(), Unexpected { kind: FN_ARGUMENTS })),
+            // NB: an empty argument list is an empty node, which is skipped
+            // when skipping tokens.
+            let arguments = match node.children().find(|n| n.value() == FN_ARGUMENTS) {
+                Some(arguments) => arguments,
+                None => return Err(Error::new(*node.span(), Unexpected { kind: FN_ARGUMENTS })),
             };
 
             let name = q.source(*name.span());
+            let function = q.ctx.functions.get(name).cloned();
+            let builtin = builtin(name);
+
+            if function.is_none() && name == "molar_mass" {
+                return molar_mass_call(q, *node.span(), arguments);
+            }
+
+            if function.is_none() && builtin.is_none() {
+                return lookup_call(q, *node.span(), name, arguments);
+            }
 
             let mut args = Vec::new();
 
             for node in arguments.children().skip_tokens() {
-                args.push(eval(q, node, bias)?);
+                args.push((*node.span(), eval(q, node, bias)?));
+            }
+
+            if let Some(function) = function {
+                return call(q, *node.span(), &function, args, bias);
             }
 
-            if let Some(builtin) = builtin(name) {
-                return builtin(*node.span(), args);
+            if let Some(builtin) = builtin {
+                return call_builtin(*node.span(), builtin, args);
             }
 
             Err(Error::new(
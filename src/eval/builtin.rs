@@ -1,72 +1,425 @@
 use crate::rational::Rational;
-use num::{One, ToPrimitive};
+use num::{BigInt, Integer, One, Signed, ToPrimitive, Zero};
 use syntree::Span;
 
 use crate::error::ErrorKind::*;
-use crate::{Error, Numeric};
+use crate::unit::Unit;
+use crate::{units, Compound, Error, Numeric};
 
 type Result<T, E = Error> = std::result::Result<T, E>;
 
-/// Expect end decode a single argument.
-fn one(range: Span<u32>, arguments: Vec<Numeric>) -> Result<Numeric> {
+/// A single argument together with its span.
+type Argument = (Span<u32>, Numeric);
+
+/// The largest number we permit calculating the factorial of.
+const MAX_FACTORIAL: u32 = 10_000;
+
+/// Expect and decode an exact number of arguments.
+fn exactly<const N: usize>(range: Span<u32>, arguments: Vec<Argument>) -> Result<[Argument; N]> {
     let actual = arguments.len();
-    let mut it = arguments.into_iter();
 
-    match (it.next(), it.next()) {
-        (Some(first), None) if actual == 1 => Ok(first),
-        _ => Err(Error::new(
-            range,
+    // NB: point at the first superfluous argument if there is one.
+    let span = arguments.get(N).map(|(span, _)| *span).unwrap_or(range);
+
+    match <[Argument; N]>::try_from(arguments) {
+        Ok(arguments) => Ok(arguments),
+        Err(..) => Err(Error::new(
+            span,
             ArgumentMismatch {
-                expected: 1,
+                expected: N,
                 actual,
             },
         )),
     }
 }
 
-/// Calculate the sine of a number.
-pub(crate) fn sin(range: Span<u32>, arguments: Vec<Numeric>) -> Result<Numeric> {
+/// Expect end decode a single argument.
+fn one(range: Span<u32>, arguments: Vec<Argument>) -> Result<Argument> {
+    let [first] = exactly(range, arguments)?;
+    Ok(first)
+}
+
+/// Expect at least one argument.
+fn at_least_one(range: Span<u32>, arguments: &[Argument]) -> Result<()> {
+    if arguments.is_empty() {
+        return Err(Error::new(
+            range,
+            ArgumentMismatch {
+                expected: 1,
+                actual: 0,
+            },
+        ));
+    }
+
+    Ok(())
+}
+
+/// Ensure that the given argument is dimensionless.
+fn dimensionless(argument: usize, (span, numeric): &Argument) -> Result<()> {
+    if !numeric.unit.is_empty() {
+        return Err(Error::new(
+            *span,
+            DimensionlessArgument {
+                argument,
+                unit: numeric.unit.clone(),
+            },
+        ));
+    }
+
+    Ok(())
+}
+
+/// Convert the given argument into the given unit.
+fn convert(argument: usize, unit: &Compound, (span, mut numeric): Argument) -> Result<Numeric> {
+    if !matches!(unit.factor(&numeric.unit, &mut numeric.value), Ok(true)) {
+        return Err(Error::new(
+            span,
+            ArgumentUnitMismatch {
+                argument,
+                expected: unit.clone(),
+                actual: numeric.unit,
+            },
+        ));
+    }
+
+    let exact = numeric.exact && numeric.unit.pi_power() == unit.pi_power();
+    Ok(Numeric::new(numeric.value, unit.clone()).with_exact(exact))
+}
+
+/// Convert a dimensionless argument into a float.
+fn float(argument: usize, arg: &Argument) -> Result<f64> {
+    dimensionless(argument, arg)?;
+
+    match arg.1.value.to_f64() {
+        Some(value) => Ok(value),
+        None => Err(Error::new(arg.0, BadArgument { argument })),
+    }
+}
+
+/// Convert a dimensionless argument into an integer.
+fn integer(argument: usize, arg: &Argument) -> Result<BigInt> {
+    dimensionless(argument, arg)?;
+
+    if !arg.1.value.is_integer() {
+        return Err(Error::new(arg.0, BadArgument { argument }));
+    }
+
+    Ok(arg.1.value.numer().clone())
+}
+
+/// Construct an approximate dimensionless value out of a float, where
+/// non-finite values are blamed on the argument at the given span.
+fn approximate(argument: usize, span: Span<u32>, value: f64) -> Result<Numeric> {
+    match Rational::from_f64(value) {
+        Some(value) => Ok(Numeric::approximate(value, Compound::empty())),
+        None => Err(Error::new(span, BadArgument { argument })),
+    }
+}
+
+/// Apply a floating point function to a single dimensionless argument.
+fn unary(range: Span<u32>, arguments: Vec<Argument>, f: fn(f64) -> f64) -> Result<Numeric> {
+    let first = one(range, arguments)?;
+    let value = float(0, &first)?;
+    approximate(0, first.0, f(value))
+}
+
+/// Construct the radian unit.
+fn radian() -> Compound {
+    Compound::from_iter([(Unit::Radian, (1, 0))])
+}
+
+/// Convert an angle argument into radians, which can either be dimensionless
+/// or have an angle unit.
+///
+/// If the angle is an exact multiple of a quarter turn, the number of quarter
+/// turns is returned as well.
+fn angle(argument: usize, arg: &Argument) -> Result<(f64, Option<BigInt>)> {
+    let (span, numeric) = arg;
+
+    let (radians, quarters) = if numeric.unit.is_empty() {
+        let quarters = numeric.value.is_zero().then(BigInt::zero);
+        (numeric.value.clone(), quarters)
+    } else {
+        let radian = radian();
+
+        if numeric.unit.base() != radian {
+            return Err(Error::new(
+                *span,
+                AngleArgument {
+                    argument,
+                    unit: numeric.unit.clone(),
+                },
+            ));
+        }
+
+        let turn = Compound::from_iter([(Unit::Derived(units::angle::TURN), (1, 0))]);
+
+        let mut turns = numeric.value.clone();
+        let mut radians = numeric.value.clone();
+
+        if !matches!(turn.factor(&numeric.unit, &mut turns), Ok(true))
+            || !matches!(radian.factor(&numeric.unit, &mut radians), Ok(true))
+        {
+            return Err(Error::new(*span, BadArgument { argument }));
+        }
+
+        let quarters = turns * Rational::new(4, 1);
+
+        let quarters = (numeric.unit.pi_power() == turn.pi_power() && quarters.is_integer())
+            .then(|| quarters.numer().clone());
+
+        (radians, quarters)
+    };
+
+    match radians.to_f64() {
+        Some(radians) => Ok((radians, quarters)),
+        None => Err(Error::new(*span, BadArgument { argument })),
+    }
+}
+
+/// Apply a trigonometric function to a single angle argument.
+///
+/// The `exact` table contains the exact result at each quarter turn, starting
+/// at zero.
+fn trigonometric(
+    range: Span<u32>,
+    arguments: Vec<Argument>,
+    f: fn(f64) -> f64,
+    exact: [Option<i32>; 4],
+) -> Result<Numeric> {
     let first = one(range, arguments)?;
+    let (radians, quarters) = angle(0, &first)?;
+
+    if let Some(quarters) = quarters {
+        let index = quarters
+            .mod_floor(&BigInt::from(4))
+            .to_usize()
+            .unwrap_or_default();
+
+        return match exact[index] {
+            Some(value) => {
+                let value = Rational::new(value, 1);
+                Ok(Numeric::new(value, Compound::empty()).with_exact(first.1.exact))
+            }
+            None => Err(Error::new(first.0, BadArgument { argument: 0 })),
+        };
+    }
+
+    approximate(0, first.0, f(radians))
+}
+
+/// Apply an inverse trigonometric function to a single dimensionless
+/// argument, the result is in radians.
+fn inverse_trigonometric(
+    range: Span<u32>,
+    arguments: Vec<Argument>,
+    f: fn(f64) -> f64,
+) -> Result<Numeric> {
+    let mut value = unary(range, arguments, f)?;
+    value.unit = radian();
+    Ok(value)
+}
+
+/// Calculate the sine of an angle.
+pub(crate) fn sin(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    trigonometric(
+        range,
+        arguments,
+        f64::sin,
+        [Some(0), Some(1), Some(0), Some(-1)],
+    )
+}
+
+/// Calculate the cosine of an angle.
+pub(crate) fn cos(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    trigonometric(
+        range,
+        arguments,
+        f64::cos,
+        [Some(1), Some(0), Some(-1), Some(0)],
+    )
+}
+
+/// Calculate the tangent of an angle.
+pub(crate) fn tan(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    trigonometric(range, arguments, f64::tan, [Some(0), None, Some(0), None])
+}
 
-    let value = match first.value.to_f64() {
-        Some(value) => value.sin(),
-        None => return Err(Error::new(range, BadArgument { argument: 0 })),
+/// Calculate the arcsine of a number in radians.
+pub(crate) fn asin(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    inverse_trigonometric(range, arguments, f64::asin)
+}
+
+/// Calculate the arccosine of a number in radians.
+pub(crate) fn acos(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    inverse_trigonometric(range, arguments, f64::acos)
+}
+
+/// Calculate the arctangent of a number in radians.
+pub(crate) fn atan(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    inverse_trigonometric(range, arguments, f64::atan)
+}
+
+/// Calculate the four quadrant arctangent of `y` and `x` in radians, which
+/// must have compatible units.
+pub(crate) fn atan2(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let [(y_span, y), x] = exactly(range, arguments)?;
+    let x_span = x.0;
+    let x = convert(1, &y.unit, x)?;
+
+    let (Some(y), Some(x)) = (y.value.to_f64(), x.value.to_f64()) else {
+        return Err(Error::new(y_span, BadArgument { argument: 0 }));
     };
 
-    let value = Rational::from_f64(value).ok_or_else(|| Error::new(range, NonFinite))?;
-    Ok(Numeric::new(value, first.unit))
+    let mut value = approximate(1, x_span, y.atan2(x))?;
+    value.unit = radian();
+    Ok(value)
+}
+
+/// Calculate the hyperbolic sine of a number.
+pub(crate) fn sinh(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    unary(range, arguments, f64::sinh)
+}
+
+/// Calculate the hyperbolic cosine of a number.
+pub(crate) fn cosh(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    unary(range, arguments, f64::cosh)
 }
 
-/// Calculate the cosine of a number.
-pub(crate) fn cos(range: Span<u32>, arguments: Vec<Numeric>) -> Result<Numeric> {
+/// Calculate the hyperbolic tangent of a number.
+pub(crate) fn tanh(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    unary(range, arguments, f64::tanh)
+}
+
+/// Calculate the exponential function of a number.
+pub(crate) fn exp(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
     let first = one(range, arguments)?;
+    dimensionless(0, &first)?;
+
+    if first.1.value.is_zero() {
+        return Ok(Numeric::new(Rational::one(), Compound::empty()).with_exact(first.1.exact));
+    }
+
+    let value = float(0, &first)?;
+    approximate(0, first.0, value.exp())
+}
 
-    let value = match first.value.to_f64() {
-        Some(value) => value.cos(),
-        None => return Err(Error::new(range, BadArgument { argument: 0 })),
+/// Calculate the base 2 logarithm of a positive integer, which unlike
+/// converting it into a float also works for very large integers.
+fn log2_int(value: &BigInt) -> f64 {
+    let shift = value.bits().saturating_sub(u64::from(f64::MANTISSA_DIGITS));
+    let top = (value >> shift).to_f64().unwrap_or(f64::NAN);
+    top.log2() + shift as f64
+}
+
+/// Calculate the base 2 logarithm of a positive rational number.
+fn log2_rational(value: &Rational) -> f64 {
+    log2_int(value.numer()) - log2_int(value.denom())
+}
+
+/// Test if `value` is `base^n`.
+///
+/// This compares bit lengths first, so that powers which are much larger than
+/// `value` are never calculated.
+fn is_power(value: &Rational, base: &Rational, n: i32) -> bool {
+    let (numer, denom) = if n < 0 {
+        (value.denom(), value.numer())
+    } else {
+        (value.numer(), value.denom())
     };
 
-    let value = Rational::from_f64(value).ok_or_else(|| Error::new(range, NonFinite))?;
-    Ok(Numeric::new(value, first.unit))
+    let abs = u64::from(n.unsigned_abs());
+    let fits = |base: &BigInt, value: &BigInt| (base.bits() - 1).saturating_mul(abs) < value.bits();
+
+    fits(base.numer(), numer) && fits(base.denom(), denom) && base.pow(n) == *value
+}
+
+/// Calculate the logarithm of `value` in the given `base`.
+///
+/// The result is exact if `value` is an exact integer power of `base`.
+fn logarithm(value: Argument, base: &Rational, base_exact: bool) -> Result<Numeric> {
+    dimensionless(0, &value)?;
+
+    if !value.1.value.numer().is_positive() {
+        return Err(Error::new(value.0, BadArgument { argument: 0 }));
+    }
+
+    let result = log2_rational(&value.1.value) / log2_rational(base);
+
+    if let Some(n) = result.round().to_i32() {
+        if is_power(&value.1.value, base, n) {
+            let exact = value.1.exact && base_exact;
+            return Ok(Numeric::new(Rational::new(n, 1), Compound::empty()).with_exact(exact));
+        }
+    }
+
+    approximate(0, value.0, result)
+}
+
+/// Calculate the natural logarithm of a number.
+pub(crate) fn ln(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let first = one(range, arguments)?;
+    dimensionless(0, &first)?;
+
+    if first.1.value.is_one() {
+        return Ok(Numeric::new(Rational::zero(), Compound::empty()).with_exact(first.1.exact));
+    }
+
+    if !first.1.value.numer().is_positive() {
+        return Err(Error::new(first.0, BadArgument { argument: 0 }));
+    }
+
+    approximate(
+        0,
+        first.0,
+        log2_rational(&first.1.value) * std::f64::consts::LN_2,
+    )
+}
+
+/// Calculate the logarithm of a number in the given base.
+pub(crate) fn log(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let [value, base] = exactly(range, arguments)?;
+    dimensionless(1, &base)?;
+
+    let (span, base) = base;
+
+    if !base.value.numer().is_positive() || base.value.is_one() {
+        return Err(Error::new(span, BadArgument { argument: 1 }));
+    }
+
+    logarithm(value, &base.value, base.exact)
+}
+
+/// Calculate the base 10 logarithm of a number.
+pub(crate) fn log10(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let first = one(range, arguments)?;
+    logarithm(first, &Rational::new(10, 1), true)
+}
+
+/// Calculate the base 2 logarithm of a number.
+pub(crate) fn log2(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let first = one(range, arguments)?;
+    logarithm(first, &Rational::new(2, 1), true)
 }
 
 /// Round a number with an optional power.
-pub(crate) fn round(range: Span<u32>, arguments: Vec<Numeric>) -> Result<Numeric> {
+pub(crate) fn round(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
     let actual = arguments.len();
+    let span = arguments.get(2).map(|(span, _)| *span).unwrap_or(range);
     let mut it = arguments.into_iter();
 
-    let (mut first, second) = match (it.next(), it.next()) {
+    let ((_, mut first), second) = match (it.next(), it.next()) {
         (Some(first), None) if actual == 1 => (first, 0),
-        (Some(first), Some(second)) if actual == 2 => (
+        (Some(first), Some((span, second))) if actual == 2 => (
             first,
             match second.value.to_i32() {
-                Some(second) => second,
-                None => return Err(Error::new(range, BadArgument { argument: 0 })),
+                Some(n) if second.value.is_integer() => n,
+                _ => return Err(Error::new(span, BadArgument { argument: 1 })),
             },
         ),
         _ => {
             return Err(Error::new(
-                range,
+                span,
                 ArgumentMismatch {
                     expected: if actual == 0 { 1 } else { 2 },
                     actual,
@@ -87,22 +440,157 @@ pub(crate) fn round(range: Span<u32>, arguments: Vec<Numeric>) -> Result<Numeric
         value
     };
 
-    debug_assert!(value.denom().is_one());
-    Ok(Numeric::new(value, first.unit))
+    Ok(Numeric::new(value, first.unit).with_exact(first.exact))
 }
 
 /// Floor a number.
-pub(crate) fn floor(range: Span<u32>, arguments: Vec<Numeric>) -> Result<Numeric> {
-    let first = one(range, arguments)?;
+pub(crate) fn floor(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let (_, first) = one(range, arguments)?;
     let value = first.value.floor();
     debug_assert!(value.denom().is_one());
-    Ok(Numeric::new(value, first.unit))
+    Ok(Numeric::new(value, first.unit).with_exact(first.exact))
 }
 
 /// Ceil a number.
-pub(crate) fn ceil(range: Span<u32>, arguments: Vec<Numeric>) -> Result<Numeric> {
-    let first = one(range, arguments)?;
+pub(crate) fn ceil(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let (_, first) = one(range, arguments)?;
     let value = first.value.ceil();
     debug_assert!(value.denom().is_one());
-    Ok(Numeric::new(value, first.unit))
+    Ok(Numeric::new(value, first.unit).with_exact(first.exact))
+}
+
+/// Calculate the absolute value of a number.
+pub(crate) fn abs(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let (_, first) = one(range, arguments)?;
+    Ok(Numeric::new(first.value.abs(), first.unit).with_exact(first.exact))
+}
+
+/// Select one out of a number of arguments with compatible units. The
+/// result is in the unit of the first argument.
+fn select(
+    range: Span<u32>,
+    arguments: Vec<Argument>,
+    replace: fn(&Rational, &Rational) -> bool,
+) -> Result<Numeric> {
+    at_least_one(range, &arguments)?;
+
+    let mut it = arguments.into_iter();
+    let mut current = match it.next() {
+        Some((_, first)) => first,
+        None => return Err(Error::new(range, MissingNode)),
+    };
+
+    for (argument, arg) in it.enumerate() {
+        let arg = convert(argument + 1, &current.unit, arg)?;
+
+        if replace(&current.value, &arg.value) {
+            current = arg;
+        }
+    }
+
+    Ok(current)
+}
+
+/// Find the smallest out of a number of values.
+pub(crate) fn min(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    select(range, arguments, |current, value| value < current)
+}
+
+/// Find the largest out of a number of values.
+pub(crate) fn max(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    select(range, arguments, |current, value| value > current)
+}
+
+/// Calculate the hypotenuse `sqrt(a^2 + b^2)` of two values with compatible
+/// units. The result is in the unit of the first argument.
+pub(crate) fn hypot(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let [(span, a), b] = exactly(range, arguments)?;
+    let b = convert(1, &a.unit, b)?;
+
+    let unit = a
+        .unit
+        .powi(2)
+        .ok_or_else(|| Error::new(span, IllegalPowerTooLarge))?;
+
+    let value = &a.value * &a.value + &b.value * &b.value;
+    let sum = Numeric::new(value, unit).with_exact(a.exact && b.exact);
+    super::pow(
+        span,
+        sum,
+        Numeric::new(Rational::new(1, 2), Compound::empty()),
+    )
+}
+
+/// Fold a number of dimensionless integer arguments.
+fn fold_integers(
+    range: Span<u32>,
+    arguments: Vec<Argument>,
+    f: fn(&BigInt, &BigInt) -> BigInt,
+) -> Result<Numeric> {
+    at_least_one(range, &arguments)?;
+
+    let mut exact = true;
+    let mut current = None::<BigInt>;
+
+    for (argument, arg) in arguments.iter().enumerate() {
+        let value = integer(argument, arg)?;
+        exact &= arg.1.exact;
+
+        current = Some(match current {
+            Some(current) => f(&current, &value),
+            None => value.abs(),
+        });
+    }
+
+    let value = current.unwrap_or_default();
+    Ok(Numeric::new(Rational::new(value, 1), Compound::empty()).with_exact(exact))
+}
+
+/// Calculate the greatest common divisor of integers.
+pub(crate) fn gcd(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    fold_integers(range, arguments, Integer::gcd)
+}
+
+/// Calculate the least common multiple of integers.
+pub(crate) fn lcm(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    fold_integers(range, arguments, Integer::lcm)
+}
+
+/// Calculate the factorial of a non-negative integer.
+pub(crate) fn factorial(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let first = one(range, arguments)?;
+    let n = integer(0, &first)?;
+
+    let n = match n.to_u32() {
+        Some(n) if n <= MAX_FACTORIAL => n,
+        _ => return Err(Error::new(first.0, BadArgument { argument: 0 })),
+    };
+
+    let mut value = BigInt::one();
+
+    for i in 2..=n {
+        value *= i;
+    }
+
+    Ok(Numeric::new(Rational::new(value, 1), Compound::empty()).with_exact(first.1.exact))
+}
+
+/// Calculate the square root of a number.
+pub(crate) fn sqrt(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let (span, first) = one(range, arguments)?;
+    super::pow(
+        span,
+        first,
+        Numeric::new(Rational::new(1, 2), Compound::empty()),
+    )
+}
+
+/// Calculate the cube root of a number.
+pub(crate) fn cbrt(range: Span<u32>, arguments: Vec<Argument>) -> Result<Numeric> {
+    let (span, first) = one(range, arguments)?;
+    super::pow(
+        span,
+        first,
+        Numeric::new(Rational::new(1, 3), Compound::empty()),
+    )
 }
@@ -0,0 +1,272 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use twox_hash::xxhash3_128::Hasher;
+
+use crate::compound::Compound;
+use crate::db::{Constant, Source};
+use crate::import::Import;
+use crate::rational::Rational;
+
+/// The seed used when hashing paths into source identifiers.
+const SEED: u64 = 0x3c1d5e7f92a4b608;
+
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct Doc<'a> {
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub(crate) source: Option<SourceDefinition<'a>>,
+    #[serde(default, borrow)]
+    pub(crate) constants: Vec<ConstantDefinition<'a>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct SourceDefinition<'a> {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) id: Option<u64>,
+    #[serde(borrow)]
+    pub(crate) description: Cow<'a, str>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub(crate) url: Option<Cow<'a, str>>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct ConstantDefinition<'a> {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tokens: Vec<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) entity: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) attribute: Option<Box<str>>,
+    pub(crate) description: Box<str>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub(crate) value: Option<Cow<'a, str>>,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub(crate) unit: Option<Cow<'a, str>>,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub(crate) exact: bool,
+    #[serde(default, borrow, skip_serializing_if = "Option::is_none")]
+    pub(crate) uncertainty: Option<Cow<'a, str>>,
+    #[serde(default, borrow, skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) series: BTreeMap<Cow<'a, str>, Cow<'a, str>>,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
+/// Facts which are indexed together with the ones shipped with the database,
+/// see [Db::open_with][crate::Db::open_with].
+///
+/// Facts are loaded from TOML documents in the same format as the ones the
+/// database is built from:
+///
+/// ```
+/// use anything::{Db, Facts};
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut facts = Facts::new();
+///
+/// facts.load_toml(r#"
+/// [[constants]]
+/// tokens = ["fleet", "size"]
+/// description = "Number of trucks in the fleet"
+/// value = "42"
+/// exact = true
+/// "#)?;
+///
+/// let db = Db::in_memory_with(&facts)?;
+/// let candidates = db.lookup("fleet size")?;
+/// assert_eq!(&*candidates[0].constant.description, "Number of trucks in the fleet");
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Facts {
+    constants: Vec<Constant>,
+    sources: Vec<Source>,
+}
+
+impl Facts {
+    /// Construct a new empty collection of facts.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load facts from the TOML file at the given path.
+    ///
+    /// The facts are attributed to a source describing the file, so that they
+    /// can be listed and described separately. The document can describe its
+    /// source in a `[source]` table with a `description` and optionally an
+    /// `id` and `url`.
+    pub fn load_path(&mut self, path: &Path) -> Result<()> {
+        let source = fs::read_to_string(path).with_context(|| anyhow!("{}", path.display()))?;
+        let doc: Doc<'_> =
+            toml::from_str(&source).with_context(|| anyhow!("{}", path.display()))?;
+
+        self.load_sourced(&doc, path)
+            .with_context(|| anyhow!("{}", path.display()))
+    }
+
+    /// Load facts which were imported from a CSV, JSON or XLSX file, see
+    /// [Import].
+    pub fn load_import(&mut self, import: &Import) -> Result<()> {
+        self.load_sourced(import.doc(), import.path())
+            .with_context(|| anyhow!("{}", import.path().display()))
+    }
+
+    /// Load a document whose facts are attributed to a source, which is
+    /// identified by the given path unless the document says otherwise.
+    fn load_sourced(&mut self, doc: &Doc<'_>, path: &Path) -> Result<()> {
+        let id = match doc.source.as_ref().and_then(|s| s.id) {
+            Some(id) => id,
+            None => {
+                let mut hash = Hasher::with_seed(SEED);
+                hash.write(path.to_string_lossy().as_bytes());
+                hash.finish_128() as u64
+            }
+        };
+
+        let source = match &doc.source {
+            Some(source) => Source {
+                id,
+                description: source.description.as_ref().into(),
+                url: source.url.as_deref().map(Box::from),
+            },
+            None => Source {
+                id,
+                description: format!("Facts from {}", path.display()).into(),
+                url: None,
+            },
+        };
+
+        let start = self.constants.len();
+        self.load_doc(doc)?;
+
+        for c in &mut self.constants[start..] {
+            c.source = Some(id);
+        }
+
+        if !self.sources.iter().any(|s| s.id == id) {
+            self.sources.push(source);
+        }
+
+        Ok(())
+    }
+
+    /// Load facts from the given TOML document.
+    ///
+    /// Values and uncertainties are decimal numbers or fractions in strings,
+    /// and units are written like `m/s^2`. Facts which change over time have
+    /// a `series` table of values by year, where the value defaults to the
+    /// most recent one. Facts which are an `attribute` of an `entity` can be
+    /// queried like `mars.mass`, and are searchable by the words of both
+    /// unless they have `tokens`. If any fact is malformed, none of the facts
+    /// in the document are loaded.
+    pub fn load_toml(&mut self, source: &str) -> Result<()> {
+        let doc: Doc<'_> = toml::from_str(source)?;
+        self.load_doc(&doc)
+    }
+
+    /// Load the facts in the given document, unless any of them is malformed.
+    pub(crate) fn load_doc(&mut self, doc: &Doc<'_>) -> Result<()> {
+        let mut constants = Vec::with_capacity(doc.constants.len());
+
+        for c in &doc.constants {
+            let mut series = BTreeMap::new();
+
+            for (year, value) in &c.series {
+                let year = str::parse::<i32>(year)
+                    .with_context(|| anyhow!("{}: bad year `{year}`", c.description))?;
+                let value = str::parse::<Rational>(value)
+                    .with_context(|| anyhow!("{}: {year}: bad value `{value}`", c.description))?;
+                series.insert(year, value);
+            }
+
+            let value = match (&c.value, series.last_key_value()) {
+                (Some(value), _) => str::parse::<Rational>(value)
+                    .with_context(|| anyhow!("{}: bad value `{value}`", c.description))?,
+                (None, Some((_, value))) => value.clone(),
+                (None, None) => return Err(anyhow!("{}: missing value", c.description)),
+            };
+
+            let unit = match &c.unit {
+                Some(unit) => str::parse::<Compound>(unit)
+                    .map_err(|error| anyhow!("{}: bad unit `{unit}`: {error}", c.description))?,
+                None => Compound::empty(),
+            };
+
+            let uncertainty = match &c.uncertainty {
+                Some(uncertainty) => {
+                    Some(str::parse::<Rational>(uncertainty).with_context(|| {
+                        anyhow!("{}: bad uncertainty `{uncertainty}`", c.description)
+                    })?)
+                }
+                None => None,
+            };
+
+            let tokens = match (&c.tokens, &c.entity, &c.attribute) {
+                (tokens, _, _) if !tokens.is_empty() => tokens.clone(),
+                (_, Some(entity), Some(attribute)) => attribute
+                    .split_whitespace()
+                    .chain(entity.split_whitespace())
+                    .map(|word| word.to_lowercase().into())
+                    .collect(),
+                _ => return Err(anyhow!("{}: missing tokens", c.description)),
+            };
+
+            if c.entity.is_some() != c.attribute.is_some() {
+                return Err(anyhow!(
+                    "{}: an entity and an attribute must be given together",
+                    c.description
+                ));
+            }
+
+            constants.push(Constant {
+                source: None,
+                tokens,
+                entity: c.entity.clone(),
+                attribute: c.attribute.clone(),
+                description: c.description.clone(),
+                value,
+                unit,
+                exact: c.exact,
+                uncertainty,
+                series,
+            });
+        }
+
+        self.constants.extend(constants);
+        Ok(())
+    }
+
+    /// The facts which have been loaded.
+    pub fn constants(&self) -> &[Constant] {
+        &self.constants
+    }
+
+    /// The sources of facts which have been loaded from files.
+    pub fn sources(&self) -> &[Source] {
+        &self.sources
+    }
+
+    /// Hash the loaded facts, so that the search index is rebuilt when they
+    /// change.
+    pub(crate) fn hash(&self, hash: &mut Hasher) -> Result<()> {
+        for c in &self.constants {
+            let bytes = serde_cbor::to_vec(c)?;
+            hash.write(&bytes.len().to_le_bytes());
+            hash.write(&bytes);
+        }
+
+        for s in &self.sources {
+            let bytes = serde_cbor::to_vec(s)?;
+            hash.write(&bytes.len().to_le_bytes());
+            hash.write(&bytes);
+        }
+
+        Ok(())
+    }
+}
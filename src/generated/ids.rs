@@ -34,6 +34,7 @@ pub const PASCAL: u32 = 3581253485;
 pub const JOULE: u32 = 3766052723;
 pub const BTU: u32 = 3481565844;
 pub const ELECTRONVOLT: u32 = 8051841;
+pub const WATT_HOUR: u32 = 1528694739;
 pub const WATT: u32 = 2843211920;
 pub const COULOMB: u32 = 4118630549;
 pub const VOLT: u32 = 658988256;
@@ -78,6 +79,12 @@ pub const TON: u32 = 3434832998;
 pub const SLUG: u32 = 686486555;
 pub const CELSIUS: u32 = 3728342790;
 pub const FAHRENHEIT: u32 = 981617578;
+pub const DEGREE: u32 = 1847197696;
+pub const ARCMINUTE: u32 = 1847197697;
+pub const ARCSECOND: u32 = 1847197698;
+pub const GRADIAN: u32 = 1847197699;
+pub const TURN: u32 = 1847197700;
+pub const BIT: u32 = 2333540352;
 pub const SPECIFIC_IMPULSE: u32 = 1147115270;
 
 /// Match the given id to the corresponding derived unit
@@ -116,6 +123,7 @@ pub fn id_to_derived(id: u32) -> Option<Derived> {
         3766052723 => Some(units::energy::JOULE),
         3481565844 => Some(units::energy::BTU),
         8051841 => Some(units::energy::ELECTRONVOLT),
+        1528694739 => Some(units::energy::WATT_HOUR),
         2843211920 => Some(units::WATT),
         4118630549 => Some(units::COULOMB),
         658988256 => Some(units::VOLT),
@@ -160,6 +168,12 @@ pub fn id_to_derived(id: u32) -> Option<Derived> {
         686486555 => Some(units::mass::SLUG),
         3728342790 => Some(units::temperature::CELSIUS),
         981617578 => Some(units::temperature::FAHRENHEIT),
+        1847197696 => Some(units::angle::DEGREE),
+        1847197697 => Some(units::angle::ARCMINUTE),
+        1847197698 => Some(units::angle::ARCSECOND),
+        1847197699 => Some(units::angle::GRADIAN),
+        1847197700 => Some(units::angle::TURN),
+        2333540352 => Some(units::information::BIT),
         1147115270 => Some(units::SPECIFIC_IMPULSE),
         _ => None,
     }
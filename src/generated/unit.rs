@@ -37,6 +37,15 @@ enum Combined {
     #[token("B")]
     #[token("byte")]
     Byte,
+    #[token("rad")]
+    #[token("radian")]
+    #[token("radians")]
+    Radian,
+    #[token("EUR")]
+    #[token("€")]
+    #[token("euro")]
+    #[token("euros")]
+    Euro,
     #[token("minute")]
     #[token("minutes")]
     #[token("min")]
@@ -160,6 +169,8 @@ enum Combined {
     #[token("electronvolt")]
     #[token("electronvolts")]
     Electronvolt,
+    #[token("Wh")]
+    WattHour,
     #[token("W")]
     #[token("watt")]
     #[token("watts")]
@@ -320,6 +331,41 @@ enum Combined {
     #[token("°F")]
     #[token("fahrenheit")]
     Fahrenheit,
+    #[token("°")]
+    #[token("deg")]
+    #[token("degs")]
+    #[token("degree")]
+    #[token("degrees")]
+    Degree,
+    #[token("′")]
+    #[token("arcmin")]
+    #[token("arcmins")]
+    #[token("arcminute")]
+    #[token("arcminutes")]
+    Arcminute,
+    #[token("″")]
+    #[token("arcsec")]
+    #[token("arcsecs")]
+    #[token("arcsecond")]
+    #[token("arcseconds")]
+    Arcsecond,
+    #[token("gon")]
+    #[token("grad")]
+    #[token("grads")]
+    #[token("gradian")]
+    #[token("gradians")]
+    Gradian,
+    #[token("turn")]
+    #[token("turns")]
+    #[token("rev")]
+    #[token("revs")]
+    #[token("revolution")]
+    #[token("revolutions")]
+    Turn,
+    #[token("b")]
+    #[token("bit")]
+    #[token("bits")]
+    Bit,
     #[token("sp")]
     SpecificImpulse,
     /// Prefixes
@@ -383,6 +429,30 @@ enum Combined {
     #[token("y")]
     #[token("yocto")]
     Yocto,
+    #[token("Ki")]
+    #[token("kibi")]
+    Kibi,
+    #[token("Mi")]
+    #[token("mebi")]
+    Mebi,
+    #[token("Gi")]
+    #[token("gibi")]
+    Gibi,
+    #[token("Ti")]
+    #[token("tebi")]
+    Tebi,
+    #[token("Pi")]
+    #[token("pebi")]
+    Pebi,
+    #[token("Ei")]
+    #[token("exbi")]
+    Exbi,
+    #[token("Zi")]
+    #[token("zebi")]
+    Zebi,
+    #[token("Yi")]
+    #[token("yobi")]
+    Yobi,
     #[token("-")]
     Separator,
 }
@@ -422,6 +492,15 @@ enum Units {
     #[token("B")]
     #[token("byte")]
     Byte,
+    #[token("rad")]
+    #[token("radian")]
+    #[token("radians")]
+    Radian,
+    #[token("EUR")]
+    #[token("€")]
+    #[token("euro")]
+    #[token("euros")]
+    Euro,
     #[token("minute")]
     #[token("minutes")]
     #[token("min")]
@@ -549,6 +628,8 @@ enum Units {
     #[token("electronvolt")]
     #[token("electronvolts")]
     Electronvolt,
+    #[token("Wh")]
+    WattHour,
     #[token("W")]
     #[token("watt")]
     #[token("watts")]
@@ -712,6 +793,41 @@ enum Units {
     #[token("°F")]
     #[token("fahrenheit")]
     Fahrenheit,
+    #[token("°")]
+    #[token("deg")]
+    #[token("degs")]
+    #[token("degree")]
+    #[token("degrees")]
+    Degree,
+    #[token("′")]
+    #[token("arcmin")]
+    #[token("arcmins")]
+    #[token("arcminute")]
+    #[token("arcminutes")]
+    Arcminute,
+    #[token("″")]
+    #[token("arcsec")]
+    #[token("arcsecs")]
+    #[token("arcsecond")]
+    #[token("arcseconds")]
+    Arcsecond,
+    #[token("gon")]
+    #[token("grad")]
+    #[token("grads")]
+    #[token("gradian")]
+    #[token("gradians")]
+    Gradian,
+    #[token("turn")]
+    #[token("turns")]
+    #[token("rev")]
+    #[token("revs")]
+    #[token("revolution")]
+    #[token("revolutions")]
+    Turn,
+    #[token("b")]
+    #[token("bit")]
+    #[token("bits")]
+    Bit,
     #[token("sp")]
     SpecificImpulse,
     #[token("-")]
@@ -740,6 +856,8 @@ pub fn parse(s: &str) -> Option<(&str, i32, Unit)> {
             Combined::Mole => Unit::Mole,
             Combined::Candela => Unit::Candela,
             Combined::Byte => Unit::Byte,
+            Combined::Radian => Unit::Radian,
+            Combined::Euro => Unit::Euro,
             Combined::Minute => Unit::Derived(units::time::MINUTE),
             Combined::Hour => Unit::Derived(units::time::HOUR),
             Combined::Day => Unit::Derived(units::time::DAY),
@@ -773,6 +891,7 @@ pub fn parse(s: &str) -> Option<(&str, i32, Unit)> {
             Combined::Joule => Unit::Derived(units::energy::JOULE),
             Combined::Btu => Unit::Derived(units::energy::BTU),
             Combined::Electronvolt => Unit::Derived(units::energy::ELECTRONVOLT),
+            Combined::WattHour => Unit::Derived(units::energy::WATT_HOUR),
             Combined::Watt => Unit::Derived(units::WATT),
             Combined::Coulomb => Unit::Derived(units::COULOMB),
             Combined::Volt => Unit::Derived(units::VOLT),
@@ -816,6 +935,12 @@ pub fn parse(s: &str) -> Option<(&str, i32, Unit)> {
             Combined::Slug => Unit::Derived(units::mass::SLUG),
             Combined::Celsius => Unit::Derived(units::temperature::CELSIUS),
             Combined::Fahrenheit => Unit::Derived(units::temperature::FAHRENHEIT),
+            Combined::Degree => Unit::Derived(units::angle::DEGREE),
+            Combined::Arcminute => Unit::Derived(units::angle::ARCMINUTE),
+            Combined::Arcsecond => Unit::Derived(units::angle::ARCSECOND),
+            Combined::Gradian => Unit::Derived(units::angle::GRADIAN),
+            Combined::Turn => Unit::Derived(units::angle::TURN),
+            Combined::Bit => Unit::Derived(units::information::BIT),
             Combined::SpecificImpulse => Unit::Derived(units::SPECIFIC_IMPULSE),
             Combined::Yotta => {
                 prefix += Prefix::YOTTA;
@@ -925,6 +1050,38 @@ pub fn parse(s: &str) -> Option<(&str, i32, Unit)> {
                 prefix += Prefix::YOCTO;
                 break;
             }
+            Combined::Kibi => {
+                prefix += Prefix::KIBI;
+                break;
+            }
+            Combined::Mebi => {
+                prefix += Prefix::MEBI;
+                break;
+            }
+            Combined::Gibi => {
+                prefix += Prefix::GIBI;
+                break;
+            }
+            Combined::Tebi => {
+                prefix += Prefix::TEBI;
+                break;
+            }
+            Combined::Pebi => {
+                prefix += Prefix::PEBI;
+                break;
+            }
+            Combined::Exbi => {
+                prefix += Prefix::EXBI;
+                break;
+            }
+            Combined::Zebi => {
+                prefix += Prefix::ZEBI;
+                break;
+            }
+            Combined::Yobi => {
+                prefix += Prefix::YOBI;
+                break;
+            }
             Combined::Separator => {
                 continue;
             }
@@ -966,6 +1123,12 @@ pub fn parse(s: &str) -> Option<(&str, i32, Unit)> {
             Units::Byte => {
                 break Unit::Byte;
             }
+            Units::Radian => {
+                break Unit::Radian;
+            }
+            Units::Euro => {
+                break Unit::Euro;
+            }
             Units::Minute => {
                 break Unit::Derived(units::time::MINUTE);
             }
@@ -1065,6 +1228,9 @@ pub fn parse(s: &str) -> Option<(&str, i32, Unit)> {
             Units::Electronvolt => {
                 break Unit::Derived(units::energy::ELECTRONVOLT);
             }
+            Units::WattHour => {
+                break Unit::Derived(units::energy::WATT_HOUR);
+            }
             Units::Watt => {
                 break Unit::Derived(units::WATT);
             }
@@ -1197,6 +1363,24 @@ pub fn parse(s: &str) -> Option<(&str, i32, Unit)> {
             Units::Fahrenheit => {
                 break Unit::Derived(units::temperature::FAHRENHEIT);
             }
+            Units::Degree => {
+                break Unit::Derived(units::angle::DEGREE);
+            }
+            Units::Arcminute => {
+                break Unit::Derived(units::angle::ARCMINUTE);
+            }
+            Units::Arcsecond => {
+                break Unit::Derived(units::angle::ARCSECOND);
+            }
+            Units::Gradian => {
+                break Unit::Derived(units::angle::GRADIAN);
+            }
+            Units::Turn => {
+                break Unit::Derived(units::angle::TURN);
+            }
+            Units::Bit => {
+                break Unit::Derived(units::information::BIT);
+            }
             Units::SpecificImpulse => {
                 break Unit::Derived(units::SPECIFIC_IMPULSE);
             }
@@ -0,0 +1,444 @@
+use std::borrow::Cow;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use calamine::{Data, Reader};
+use serde::Deserialize;
+
+use crate::facts::{ConstantDefinition, Doc, Facts, SourceDefinition};
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Mapping<'a> {
+    file: PathBuf,
+    #[serde(default)]
+    format: Option<Format>,
+    #[serde(default)]
+    sheet: Option<String>,
+    #[serde(default)]
+    records: Option<String>,
+    #[serde(default)]
+    delimiter: Option<char>,
+    entity: String,
+    #[serde(default, borrow)]
+    source: Option<SourceDefinition<'a>>,
+    #[serde(default)]
+    attributes: Vec<AttributeMapping>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AttributeMapping {
+    attribute: String,
+    column: String,
+    #[serde(default)]
+    unit: Option<String>,
+    #[serde(default)]
+    uncertainty: Option<String>,
+    #[serde(default)]
+    exact: bool,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    Csv,
+    Json,
+    Xlsx,
+}
+
+/// A table of cells, where the first row names the columns.
+struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<Option<String>>>,
+}
+
+impl Table {
+    /// Get the index of the named column.
+    fn column(&self, name: &str) -> Result<usize> {
+        match self.columns.iter().position(|c| c == name) {
+            Some(index) => Ok(index),
+            None => bail!("missing column `{name}`"),
+        }
+    }
+}
+
+/// Facts imported from a local CSV, JSON or XLSX file, as described by a
+/// mapping file.
+///
+/// The mapping is a TOML document naming the file relative to the mapping,
+/// the column which holds the name of each entity, and the columns which
+/// hold its attributes:
+///
+/// ```toml
+/// file = "fleet.csv"
+/// entity = "Truck"
+///
+/// [source]
+/// description = "Fleet spreadsheet"
+///
+/// [[attributes]]
+/// attribute = "range"
+/// column = "Range (km)"
+/// unit = "km"
+/// uncertainty = "Range error (km)"
+/// ```
+///
+/// Every row becomes one fact per attribute, like `truck_7.range`, and empty
+/// cells are skipped. The format is taken from the extension of the file
+/// unless `format` is given. XLSX files are read from their first worksheet
+/// unless `sheet` is given, and JSON files are an array of objects, or have
+/// one at the [JSON pointer] given in `records`. CSV files are comma separated
+/// unless `delimiter` is given.
+///
+/// Each fact is described as `Range of Truck 7` unless the attribute has a
+/// `description` like `"Range of the truck {entity}"`.
+///
+/// [JSON pointer]: https://datatracker.ietf.org/doc/html/rfc6901
+pub struct Import {
+    path: PathBuf,
+    doc: Doc<'static>,
+}
+
+impl Import {
+    /// Import the facts described by the mapping file at the given path.
+    pub fn open(mapping: &Path) -> Result<Self> {
+        Self::open_inner(mapping).with_context(|| anyhow!("{}", mapping.display()))
+    }
+
+    fn open_inner(path: &Path) -> Result<Self> {
+        let mapping = fs::read_to_string(path)?;
+        let mapping: Mapping<'_> = toml::from_str(&mapping)?;
+
+        let file = match path.parent() {
+            Some(parent) => parent.join(&mapping.file),
+            None => mapping.file.clone(),
+        };
+
+        let format = match (mapping.format, file.extension().and_then(|e| e.to_str())) {
+            (Some(format), _) => format,
+            (None, Some(e)) if e.eq_ignore_ascii_case("csv") => Format::Csv,
+            (None, Some(e)) if e.eq_ignore_ascii_case("json") => Format::Json,
+            (None, Some(e)) if e.eq_ignore_ascii_case("xlsx") => Format::Xlsx,
+            (None, _) => bail!(
+                "{}: unknown format, expected csv, json or xlsx",
+                file.display()
+            ),
+        };
+
+        let bytes = fs::read(&file).with_context(|| anyhow!("{}", file.display()))?;
+
+        let table = match format {
+            Format::Csv => read_csv(&bytes, mapping.delimiter.unwrap_or(',')),
+            Format::Json => read_json(&bytes, mapping.records.as_deref()),
+            Format::Xlsx => read_xlsx(bytes, mapping.sheet.as_deref()),
+        }
+        .with_context(|| anyhow!("{}", file.display()))?;
+
+        let entity = table.column(&mapping.entity)?;
+
+        let mut columns = Vec::with_capacity(mapping.attributes.len());
+
+        for a in &mapping.attributes {
+            let uncertainty = match &a.uncertainty {
+                Some(column) => Some(table.column(column)?),
+                None => None,
+            };
+
+            columns.push((a, table.column(&a.column)?, uncertainty));
+        }
+
+        let mut constants = Vec::new();
+
+        for row in &table.rows {
+            let Some(name) = row[entity].as_deref() else {
+                continue;
+            };
+
+            for &(a, value, uncertainty) in &columns {
+                let Some(value) = row[value].clone() else {
+                    continue;
+                };
+
+                let description = match &a.description {
+                    Some(description) => description.replace("{entity}", name),
+                    None => format!("{} of {name}", capitalize(&a.attribute)),
+                };
+
+                constants.push(ConstantDefinition {
+                    entity: Some(name.into()),
+                    attribute: Some(a.attribute.as_str().into()),
+                    description: description.into(),
+                    value: Some(Cow::Owned(value)),
+                    unit: a.unit.clone().map(Cow::Owned),
+                    exact: a.exact,
+                    uncertainty: uncertainty.and_then(|n| row[n].clone()).map(Cow::Owned),
+                    ..ConstantDefinition::default()
+                });
+            }
+        }
+
+        let source = match mapping.source {
+            Some(source) => SourceDefinition {
+                id: source.id,
+                description: source.description.into_owned().into(),
+                url: source.url.map(|url| url.into_owned().into()),
+            },
+            None => SourceDefinition {
+                id: None,
+                description: format!("Facts imported from {}", mapping.file.display()).into(),
+                url: None,
+            },
+        };
+
+        let doc = Doc {
+            source: Some(source),
+            constants,
+        };
+
+        // NB: check that every fact can be loaded, so that malformed values
+        // and units are reported when importing.
+        Facts::new().load_doc(&doc)?;
+
+        Ok(Self {
+            path: path.to_owned(),
+            doc,
+        })
+    }
+
+    /// The number of facts which were imported.
+    pub fn len(&self) -> usize {
+        self.doc.constants.len()
+    }
+
+    /// Test if no facts were imported.
+    pub fn is_empty(&self) -> bool {
+        self.doc.constants.is_empty()
+    }
+
+    /// Format the imported facts as a TOML document, which can be loaded with
+    /// [Facts::load_path][crate::Facts::load_path].
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(&self.doc)?)
+    }
+
+    /// The path to the mapping file.
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The imported document.
+    pub(crate) fn doc(&self) -> &Doc<'static> {
+        &self.doc
+    }
+}
+
+/// Capitalize the first letter of the given string.
+fn capitalize(s: &str) -> String {
+    let mut it = s.chars();
+
+    match it.next() {
+        Some(c) => c.to_uppercase().chain(it).collect(),
+        None => String::new(),
+    }
+}
+
+/// Convert a cell into an optional string, where empty cells are `None`.
+fn cell(value: &str) -> Option<String> {
+    let value = value.trim();
+
+    if value.is_empty() {
+        return None;
+    }
+
+    Some(value.to_owned())
+}
+
+/// Read a CSV file, where fields can be quoted with `"` and quotes are
+/// escaped by doubling them.
+fn read_csv(bytes: &[u8], delimiter: char) -> Result<Table> {
+    let text = std::str::from_utf8(bytes)?;
+    let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut it = text.chars().peekable();
+
+    while let Some(c) = it.next() {
+        match c {
+            '"' if quoted => {
+                if it.peek() == Some(&'"') {
+                    it.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.is_empty() => {
+                quoted = true;
+            }
+            c if c == delimiter && !quoted => {
+                record.push(std::mem::take(&mut field));
+            }
+            '\r' if !quoted && it.peek() == Some(&'\n') => {}
+            '\n' if !quoted => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c => {
+                field.push(c);
+            }
+        }
+    }
+
+    if quoted {
+        bail!("unterminated quoted field");
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    let mut records = records
+        .into_iter()
+        .filter(|r| !(r.len() == 1 && r[0].trim().is_empty()));
+
+    let Some(columns) = records.next() else {
+        bail!("missing header row");
+    };
+
+    let columns = columns
+        .into_iter()
+        .map(|c| c.trim().to_owned())
+        .collect::<Vec<_>>();
+
+    let mut rows = Vec::new();
+
+    for (n, record) in records.enumerate() {
+        if record.len() > columns.len() {
+            bail!(
+                "row {}: expected at most {} fields, but got {}",
+                n + 1,
+                columns.len(),
+                record.len()
+            );
+        }
+
+        let mut row = record.iter().map(|f| cell(f)).collect::<Vec<_>>();
+        row.resize(columns.len(), None);
+        rows.push(row);
+    }
+
+    Ok(Table { columns, rows })
+}
+
+/// Read a JSON file containing an array of objects, optionally at the given
+/// JSON pointer.
+fn read_json(bytes: &[u8], records: Option<&str>) -> Result<Table> {
+    let root: serde_json::Value = serde_json::from_slice(bytes)?;
+
+    let records = match records {
+        Some(pointer) => match root.pointer(pointer) {
+            Some(records) => records,
+            None => bail!("nothing at `{pointer}`"),
+        },
+        None => &root,
+    };
+
+    let Some(records) = records.as_array() else {
+        bail!("expected an array of objects");
+    };
+
+    let mut columns = Vec::<String>::new();
+    let mut rows = Vec::with_capacity(records.len());
+
+    for (n, record) in records.iter().enumerate() {
+        let Some(record) = record.as_object() else {
+            bail!("record {n}: expected an object");
+        };
+
+        let mut row = vec![None; columns.len()];
+
+        for (key, value) in record {
+            let value = match value {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(s) => cell(s),
+                serde_json::Value::Number(n) => Some(n.to_string()),
+                _ => bail!("record {n}: `{key}`: expected a string or a number"),
+            };
+
+            let index = match columns.iter().position(|c| c == key) {
+                Some(index) => index,
+                None => {
+                    columns.push(key.clone());
+                    row.push(None);
+                    columns.len() - 1
+                }
+            };
+
+            row[index] = value;
+        }
+
+        rows.push(row);
+    }
+
+    for row in &mut rows {
+        row.resize(columns.len(), None);
+    }
+
+    Ok(Table { columns, rows })
+}
+
+/// Read a worksheet from an XLSX file, by name or the first one.
+fn read_xlsx(bytes: Vec<u8>, sheet: Option<&str>) -> Result<Table> {
+    let mut doc = calamine::Xlsx::new(Cursor::new(bytes))?;
+
+    let range = match sheet {
+        Some(sheet) => doc.worksheet_range(sheet)?,
+        None => match doc.worksheet_range_at(0) {
+            Some(range) => range?,
+            None => bail!("missing worksheet"),
+        },
+    };
+
+    let mut it = range.rows();
+
+    let Some(header) = it.next() else {
+        bail!("missing header row");
+    };
+
+    let columns = header
+        .iter()
+        .map(|c| c.to_string().trim().to_owned())
+        .collect::<Vec<_>>();
+
+    let mut rows = Vec::new();
+
+    for (n, record) in it.enumerate() {
+        let mut row = Vec::with_capacity(columns.len());
+
+        for (column, value) in columns.iter().zip(record) {
+            row.push(match value {
+                Data::Empty => None,
+                Data::String(s) => cell(s),
+                // NB: the shortest representation which round-trips, so that
+                // a cell with `0.1` isn't imported as a binary fraction.
+                Data::Float(f) => Some(f.to_string()),
+                Data::Int(i) => Some(i.to_string()),
+                _ => bail!("row {}: `{column}`: expected a string or a number", n + 1),
+            });
+        }
+
+        row.resize(columns.len(), None);
+        rows.push(row);
+    }
+
+    Ok(Table { columns, rows })
+}
@@ -17,27 +17,196 @@
 //!
 //! * Unit conversions!
 //!   * `any 3dl to m^3` gives us `0.0003 m³`.
-//!   * `any 1000Gbtu to MWh` gives us `293055.555555555555… hr⋅MW`.
+//!   * `any 1000Gbtu to MWh` gives us `293055.555555555555… MWh`.
+//!   * `any 90 deg to arcmin` gives us `5400 ′`, and `any sin(90°)` gives us `1`.
+//!   * `any 100 Mbit/s * 1 hr to GiB` gives us `41.909515857696… GiB`.
 //! * Fact queries!
 //!   * `any population finland / population world` gives us
-//!     `0.000710822459005…`.
+//!     `≈ 0.000710822459005…`.
 //! * Basic math!
-//!   * `any 32500 / round(population finland)` gives us `0.00586566578555…`.
+//!   * `any 32500 / round(population finland)` gives us `≈ 0.00586566578555…`.
 //! * Unit-aware calculations!
 //!   * `any 3N / 10kg` gives us `0.3 m/s²`.
+//!   * `any 230 V * 16 A * 8 hr` gives us `105.984 MJ`.
+//! * Mixed units!
+//!   * `any 180 cm to ft+in` gives us `5 ft 10.866141732283… in`.
+//!   * `any 8000 s to hms` gives us `2 hr 13 min 20 s`, and `any 5ft 11in to cm`
+//!     gives us `180.34 cm`.
+//! * Variables and functions!
+//!   * `any "mass = 3kg; mass to g"` gives us `3 kg` followed by `3000 g`.
+//!   * `any "kinetic(m: kg, v: m/s) = 0.5 * m * v * v; kinetic(2kg, 3m/s) to J"`
+//!     gives us `9 J`.
+//! * Your own units!
+//!   * `any "unit smoot = 1.7018 m; 1km to smoot"` gives us `587.613115524738… smoot`.
+//! * Measurement uncertainty!
+//!   * `any "(9.81 ± 0.02 m/s^2) * 2 s"` gives us `19.620 ± 0.040 m/s`.
+//! * Dates and times!
+//!   * `any 2026-10-18 + 90 dy` gives us `2027-01-16`.
+//!   * `any 2027-01-01 - 2026-10-18 to wk` gives us `10.714285714285… wk`.
+//!   * `any 2026-10-18T09:30+02:00 to utc` gives us `2026-10-18T07:30:00Z`.
 //! * And a bit more...
 //!
+//! Running `any` without a query (or with `--repl`) starts an interactive
+//! session, where variables and the previous result (`_` or `ans`) are kept
+//! between lines.
+//!
+//! Results which are approximate, like measured constants or irrational roots,
+//! are prefixed with `≈`. With `--exact` such results are refused instead of
+//! being printed as a misleading fraction.
+//!
+//! Facts can be attributes of an entity, like the floor area of a depot in your
+//! own facts below, and are looked up exactly with `depot.floor_area`,
+//! `floor area of the depot` or `floor_area(depot)`. Names with spaces are written
+//! with underscores in the dotted form, like `truck_7.range`, and `depot.colour`
+//! lists the attributes that the depot does have. The bundled planets and
+//! countries aren't entities yet, so they are looked up like other facts.
+//!
+//! Other facts are looked up by how well the query matches them. If several
+//! facts match about equally well, like `earth` matching its mass, radius and
+//! diameter, the query is reported as ambiguous with the alternatives listed,
+//! rather than guessing. `--candidates` shows the facts that were considered and their scores.
+//!
+//! The fact database can be browsed with `any search <terms>`, which lists the
+//! matching facts with their tokens, value and source. `any list` lists the
+//! sources of the database, and `any list --source <id>` every fact from one of
+//! them.
+//!
+//! Your own facts can be added in TOML files, in the same `[[constants]]` format
+//! as the database is built from. They are loaded from every `.toml` file in the
+//! `facts` directory in the configuration directory, or with `--facts <path>`,
+//! and the search index is rebuilt whenever they change. A fact can be an
+//! `attribute` of an `entity` instead of having `tokens`, like `entity = "Depot"`
+//! and `attribute = "floor area"` for `depot.floor_area`.
+//!
+//! ```toml
+//! [[constants]]
+//! tokens = ["fleet", "size"]
+//! description = "Number of trucks in the fleet"
+//! value = "42"
+//! exact = true
+//! ```
+//!
+//! Facts which change over time have a `series` table of values by year instead
+//! of a single `value`. With the fact below they can be queried in a given year
+//! with `population testland in 2005` or `population(testland, 2000)`, which uses
+//! the most recent value on or before that year. Without a year the latest value
+//! is used. The bundled populations don't have series yet, so they only have a
+//! single value.
+//!
+//! ```toml
+//! [[constants]]
+//! tokens = ["population", "testland"]
+//! description = "Population of Testland"
+//! series = { "1990" = "1000", "2000" = "1500", "2010" = "2250" }
+//! ```
+//!
+//! Local CSV, JSON or XLSX files like spreadsheets can be imported with
+//! `any db import <mapping>`, where the mapping is a TOML file saying which
+//! column names the entities and which columns hold their attributes. The
+//! imported facts are stored in the `facts` directory, so with the mapping below
+//! `truck_7.range` is the value in the `Range (km)` column of the row for
+//! `Truck 7`.
+//!
+//! ```toml
+//! file = "fleet.csv"
+//! entity = "Truck"
+//!
+//! [source]
+//! description = "Fleet spreadsheet"
+//!
+//! [[attributes]]
+//! attribute = "range"
+//! column = "Range (km)"
+//! unit = "km"
+//! ```
+//!
+//! Chemistry uses the atomic masses of the elements, which aren't bundled. They
+//! are added as your own facts, with an `entity` like `Hydrogen` and an
+//! `attribute` of `atomic mass` in `Da`. With the elements of a formula defined,
+//! `molar_mass(H2SO4)` is its molar mass in `g/mol`, and a mass or an amount of
+//! substance followed by the formula converts between the two, like
+//! `18.015 g H2O to mol` or `2 mol NaCl`, unless it is cast to what it already
+//! is. A single unit is never a formula, so `1 mol K` is in `K⋅mol`. Groups in
+//! parentheses can't be written in a query, so `Ca(OH)2` is written as `CaO2H2`.
+//!
+//! ```toml
+//! [[constants]]
+//! entity = "Hydrogen"
+//! attribute = "atomic mass"
+//! description = "Atomic mass of Hydrogen"
+//! value = "1.008"
+//! unit = "Da"
+//! ```
+//!
+//! Values can carry a standard uncertainty, like `9.81 ± 0.02` or `10 ± 5%`,
+//! which is propagated to first order through arithmetic, powers and functions.
+//! Measured constants from the database include their uncertainty where it is
+//! known. Very large or small values are shown like `(6.67430 ± 0.00015)e-11`.
+//!
+//! Dates like `2026-10-18` and timestamps like `2026-10-18T09:30:00+02:00` are
+//! instants in time, as are `now` and `today`. Instants can be offset by
+//! durations, and subtracting two instants gives the duration between them, but
+//! adding them together is an error. `to unix` gives the number of seconds since
+//! the Unix epoch, and `to utc` shows an instant in UTC. Only fixed UTC offsets
+//! are supported, and `today` starts at midnight UTC.
+//!
+//! Unless a query contains a `to` cast, results are shown in their most readable
+//! unit: a named unit like `J` or `W` if the dimensions match, with an SI prefix
+//! that keeps the value between 1 and 1000. `to auto` does the same explicitly,
+//! and `--no-auto` keeps results in the unit they were calculated in.
+//!
+//! Results are shown with a consistent precision with `--sig-figs <n>` for
+//! significant figures, `--decimals <n>` for decimal places, or `--scientific
+//! <n>` and `--engineering <n>` for scientific and engineering notation with `n`
+//! significant figures. `any --engineering 3 "1 mi / 1 ms to m/s"` gives us
+//! `1.61e6 m/s`.
+//!
+//! Custom units can also be loaded from `units.toml` in the configuration
+//! directory, or with `--units <path>`.
+//!
+//! Currencies are converted using exchange rates loaded from `rates.toml` in the
+//! configuration directory, or with `--rates <path>`. The reference rates of the
+//! ECB are downloaded by `cargo run -p assets`, but aren't shipped yet.
+//! `--as-of <YYYY-MM-DD>` picks the most recent rates on or before a given date,
+//! and `--describe` cites the rate and where it came from. Rates are the number of
+//! units of a currency per euro.
+//!
+//! ```toml
+//! [[days]]
+//! date = "2024-12-31"
+//! rates = { USD = "1.25", SEK = "11.5" }
+//! ```
+//!
+//! Many queries can be evaluated at once with `any -f calcs.txt`, or from
+//! standard input with `any -`. Each line is evaluated in order, sharing
+//! variables, and empty lines and lines starting with `#` are skipped. Errors are
+//! reported with the line they occurred on, and evaluation continues with the next
+//! line. The exit code is non-zero if any line failed.
+//!
+//! For scripts, `--format json` prints a single JSON document per query and
+//! `--format ndjson` prints one JSON document per line for each result and
+//! description. Values include their exact numerator and denominator, a decimal
+//! rendering, any uncertainty, and their unit in terms of base dimensions.
+//! Errors include the byte range of the input they refer to.
+//!
 //! You can think of `any` is a **much** smaller and local wolfram engine,
 //! without the hassle of having to go online for your answers.
 
 #![deny(missing_docs)]
 
+mod auto;
+mod check;
+mod chemistry;
 mod compound;
 mod config;
+mod currency;
+mod date;
 mod db;
 mod error;
 mod eval;
+mod facts;
 mod generated;
+mod import;
 mod numeric;
 mod powers;
 mod prefix;
@@ -47,13 +216,21 @@ pub mod rational;
 pub mod syntax;
 mod unit;
 mod unit_parser;
+mod unit_registry;
 pub mod units;
 
+pub use self::check::{check, Problem, Severity};
 pub use self::compound::Compound;
-pub use self::db::{Constant, Db, Source};
+pub use self::config::{config_dir, data_dir};
+pub use self::currency::{Day, ExchangeRate, Rates};
+pub use self::db::{Candidate, Constant, Db, LookupError, Source};
 pub use self::error::Error;
-pub use self::numeric::Numeric;
+pub use self::eval::Context;
+pub use self::facts::Facts;
+pub use self::import::Import;
+pub use self::numeric::{Kind, Numeric};
 pub use self::powers::Powers;
 pub use self::query::{parse, query, Description, Options, Query};
 pub use self::rational::Rational;
 pub use self::unit::Unit;
+pub use self::unit_registry::{UnitDefinitionError, UnitRegistry};
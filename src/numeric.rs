@@ -1,5 +1,28 @@
+use num::{Signed, Zero};
+
 use crate::compound::Compound;
-use crate::rational::Rational;
+use crate::date;
+use crate::rational::{DisplaySpec, Rational};
+use crate::unit::Unit;
+
+/// The kind of a numerical value.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Kind {
+    /// A quantity like a length or a duration.
+    #[default]
+    Quantity,
+    /// An instant in time, where the value is the number of seconds since the
+    /// Unix epoch `1970-01-01T00:00:00Z`.
+    ///
+    /// Instants can be offset by durations, and subtracting one instant from
+    /// another gives the duration between them.
+    Instant {
+        /// The UTC offset in minutes which is used when displaying the
+        /// instant.
+        offset: i32,
+    },
+}
 
 /// A arbitrary precision numerical value with a unit.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -9,11 +32,261 @@ pub struct Numeric {
     pub value: Rational,
     /// The compound unit of this numeric type.
     pub unit: Compound,
+    /// Whether the value is exact. Values which are calculated using floating
+    /// point, approximated roots, or measured constants are not exact.
+    pub exact: bool,
+    /// The standard uncertainty of the value in the same unit, if the value
+    /// is a measurement or calculated from one.
+    pub uncertainty: Option<Rational>,
+    /// Units to break the value down into when it is displayed, like `ft`
+    /// and `in`. The value is expressed in the last of these units.
+    pub mixed: Option<Box<[Compound]>>,
+    /// The kind of the value.
+    pub kind: Kind,
 }
 
 impl Numeric {
-    /// Construct a new numerical value.
+    /// Construct a new exact numerical value.
     pub fn new(value: Rational, unit: Compound) -> Self {
-        Self { value, unit }
+        Self {
+            value,
+            unit,
+            exact: true,
+            uncertainty: None,
+            mixed: None,
+            kind: Kind::Quantity,
+        }
+    }
+
+    /// Construct a new approximate numerical value.
+    ///
+    /// ```
+    /// use anything::{Compound, Numeric, Rational};
+    ///
+    /// let value = Numeric::approximate(Rational::new(314, 100), Compound::empty());
+    /// assert!(!value.exact);
+    /// ```
+    pub fn approximate(value: Rational, unit: Compound) -> Self {
+        Self {
+            value,
+            unit,
+            exact: false,
+            uncertainty: None,
+            mixed: None,
+            kind: Kind::Quantity,
+        }
+    }
+
+    /// Set the standard uncertainty of this value.
+    ///
+    /// ```
+    /// use anything::rational::DisplaySpec;
+    /// use anything::{Compound, Numeric, Rational};
+    ///
+    /// let value = Numeric::new(Rational::new(981, 100), Compound::empty())
+    ///     .with_uncertainty(Rational::new(2, 100));
+    ///
+    /// let spec = DisplaySpec::default();
+    /// assert_eq!(value.display_uncertainty(&spec).as_deref(), Some("9.810 ± 0.020"));
+    /// ```
+    pub fn with_uncertainty(self, uncertainty: Rational) -> Self {
+        Self {
+            uncertainty: Some(uncertainty.abs()),
+            ..self
+        }
+    }
+
+    /// Format the value together with its uncertainty as `value ±
+    /// uncertainty`, with the uncertainty rounded to two significant digits
+    /// and the value rounded to the same precision. Values whose exponent
+    /// reaches the [DisplaySpec::exponent_limit] of the given specification are
+    /// formatted like `(value ± uncertainty)e-11`.
+    ///
+    /// Returns `None` if the value has no uncertainty.
+    ///
+    /// ```
+    /// use anything::rational::DisplaySpec;
+    /// use anything::{Compound, Numeric, Rational};
+    ///
+    /// let spec = DisplaySpec::default();
+    ///
+    /// let value = Numeric::new(Rational::new(667430, 10000), Compound::empty())
+    ///     .with_uncertainty(Rational::new(15, 10000));
+    ///
+    /// assert_eq!(value.display_uncertainty(&spec).as_deref(), Some("66.7430 ± 0.0015"));
+    ///
+    /// let value = Numeric::new(Rational::new(123456, 1), Compound::empty())
+    ///     .with_uncertainty(Rational::new(789, 1));
+    ///
+    /// assert_eq!(value.display_uncertainty(&spec).as_deref(), Some("123460 ± 790"));
+    ///
+    /// let value = Numeric::new(Rational::new(667430, 10000000000000000u64), Compound::empty())
+    ///     .with_uncertainty(Rational::new(15, 10000000000000000u64));
+    ///
+    /// assert_eq!(value.display_uncertainty(&spec).as_deref(), Some("(6.67430 ± 0.00015)e-11"));
+    /// ```
+    pub fn display_uncertainty(&self, spec: &DisplaySpec) -> Option<String> {
+        let uncertainty = self.uncertainty.as_ref()?;
+
+        if uncertainty.is_zero() {
+            return None;
+        }
+
+        let decimals = 1 - uncertainty.magnitude();
+
+        let mut exponent = uncertainty.magnitude();
+
+        if !self.value.is_zero() {
+            exponent = exponent.max(self.value.magnitude());
+        }
+
+        // NB: very large or small values share an exponent, just like
+        // values without an uncertainty are displayed.
+        if exponent.unsigned_abs() as usize >= spec.exponent_limit {
+            let scale = Rational::new(10, 1).pow(-exponent);
+
+            return Some(format!(
+                "({} ± {})e{exponent}",
+                (&self.value * &scale).display_rounded(decimals + exponent),
+                (uncertainty * &scale).display_rounded(decimals + exponent)
+            ));
+        }
+
+        Some(format!(
+            "{} ± {}",
+            self.value.display_rounded(decimals),
+            uncertainty.display_rounded(decimals)
+        ))
+    }
+
+    /// Construct an instant in time from the number of seconds since the Unix
+    /// epoch, which is displayed using the given UTC offset in minutes.
+    ///
+    /// ```
+    /// use anything::{Numeric, Rational};
+    ///
+    /// let value = Numeric::instant(Rational::new(1792281600, 1), 0);
+    /// assert_eq!(value.display_instant().as_deref(), Some("2026-10-18"));
+    ///
+    /// let value = Numeric::instant(Rational::new(1792315800, 1), 120);
+    /// assert_eq!(value.display_instant().as_deref(), Some("2026-10-18T11:30:00+02:00"));
+    /// ```
+    pub fn instant(value: Rational, offset: i32) -> Self {
+        Self {
+            unit: Compound::from_iter([(Unit::Second, (1, 0))]),
+            kind: Kind::Instant { offset },
+            ..Self::new(value, Compound::empty())
+        }
+    }
+
+    /// Test if the value is an instant in time.
+    pub fn is_instant(&self) -> bool {
+        matches!(self.kind, Kind::Instant { .. })
+    }
+
+    /// Format an instant in time as a date like `2026-10-18` if it is at
+    /// midnight UTC, or as a timestamp like `2026-10-18T09:30:00Z` otherwise.
+    ///
+    /// Returns `None` if the value isn't an instant, or if it is too far from
+    /// the Unix epoch to be shown as a date.
+    pub fn display_instant(&self) -> Option<String> {
+        let Kind::Instant { offset } = self.kind else {
+            return None;
+        };
+
+        date::format(&self.value, offset)
+    }
+
+    /// Set the kind of this value.
+    pub(crate) fn with_kind(self, kind: Kind) -> Self {
+        Self { kind, ..self }
+    }
+
+    /// Break the value down into its mixed units, with the remainder carried
+    /// into the last unit. Leading units which would be zero are omitted.
+    ///
+    /// Returns `None` if the value isn't expressed in mixed units.
+    ///
+    /// ```
+    /// use anything::{Compound, Numeric, Rational};
+    ///
+    /// let ft = str::parse::<Compound>("ft")?;
+    /// let inch = str::parse::<Compound>("in")?;
+    ///
+    /// let value = Numeric::new(Rational::new(143, 2), inch.clone())
+    ///     .with_mixed([ft.clone(), inch.clone()]);
+    ///
+    /// assert_eq!(
+    ///     value.parts(),
+    ///     Some(vec![(Rational::new(5, 1), ft), (Rational::new(23, 2), inch)])
+    /// );
+    /// # Ok::<_, anyhow::Error>(())
+    /// ```
+    pub fn parts(&self) -> Option<Vec<(Rational, Compound)>> {
+        let (last, units) = self.mixed.as_deref()?.split_last()?;
+
+        let neg = self.value.numer().is_negative();
+        let mut rem = self.value.abs();
+        let mut parts = Vec::with_capacity(units.len() + 1);
+
+        for unit in units {
+            let mut whole = rem.clone();
+
+            if !matches!(unit.factor(last, &mut whole), Ok(true)) {
+                return None;
+            }
+
+            let whole = whole.floor();
+
+            if parts.is_empty() && whole.is_zero() {
+                continue;
+            }
+
+            let mut taken = whole.clone();
+
+            if !matches!(last.factor(unit, &mut taken), Ok(true)) {
+                return None;
+            }
+
+            rem -= taken;
+            parts.push((whole, unit.clone()));
+        }
+
+        parts.push((rem, last.clone()));
+
+        // NB: the sign is carried by the first part, so that `-5 ft 11 in`
+        // reads as negative.
+        if neg {
+            if let Some((value, _)) = parts.first_mut() {
+                *value = Rational::new(0, 1) - value.clone();
+            }
+        }
+
+        Some(parts)
+    }
+
+    /// Display the value broken down into the given units, like `ft` and
+    /// `in`. The value must already be expressed in the last of the units.
+    pub fn with_mixed(self, units: impl IntoIterator<Item = Compound>) -> Self {
+        Self {
+            mixed: Some(units.into_iter().collect()),
+            ..self
+        }
+    }
+
+    /// Set the uncertainty of this value, if any.
+    pub(crate) fn uncertain(self, uncertainty: Option<Rational>) -> Self {
+        Self {
+            uncertainty,
+            ..self
+        }
+    }
+
+    /// Mark this value as approximate unless `exact` is set.
+    pub(crate) fn with_exact(self, exact: bool) -> Self {
+        Self {
+            exact: self.exact && exact,
+            ..self
+        }
     }
 }
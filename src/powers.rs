@@ -168,7 +168,7 @@ impl Iterator for Iter<'_> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let (unit, power) = self.iter.next()?;
-        Some((*unit, *power))
+        Some((unit.clone(), *power))
     }
 }
 
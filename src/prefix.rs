@@ -1,5 +1,23 @@
 use std::fmt;
 
+use crate::rational::Rational;
+
+/// Binary prefixes are encoded as an offset from this value, where the offset
+/// is the power of 2 that the prefix corresponds to. This keeps them apart
+/// from decimal prefixes, which are encoded as a power of 10.
+const BINARY: i32 = 1 << 16;
+
+const BINARY_PREFIXES: [(i32, Prefix); 8] = [
+    (Prefix::KIBI, Prefix::Kibi),
+    (Prefix::MEBI, Prefix::Mebi),
+    (Prefix::GIBI, Prefix::Gibi),
+    (Prefix::TEBI, Prefix::Tebi),
+    (Prefix::PEBI, Prefix::Pebi),
+    (Prefix::EXBI, Prefix::Exbi),
+    (Prefix::ZEBI, Prefix::Zebi),
+    (Prefix::YOBI, Prefix::Yobi),
+];
+
 const PREFIXES: [(i32, Prefix); 21] = [
     (Prefix::YOCTO, Prefix::Yocto),
     (Prefix::ZEPTO, Prefix::Zepto),
@@ -49,6 +67,14 @@ pub enum Prefix {
     Atto,
     Zepto,
     Yocto,
+    Kibi,
+    Mebi,
+    Gibi,
+    Tebi,
+    Pebi,
+    Exbi,
+    Zebi,
+    Yobi,
 }
 
 impl Prefix {
@@ -73,13 +99,44 @@ impl Prefix {
     pub const EXA: i32 = 18;
     pub const ZETTA: i32 = 21;
     pub const YOTTA: i32 = 24;
+    pub const KIBI: i32 = BINARY + 10;
+    pub const MEBI: i32 = BINARY + 20;
+    pub const GIBI: i32 = BINARY + 30;
+    pub const TEBI: i32 = BINARY + 40;
+    pub const PEBI: i32 = BINARY + 50;
+    pub const EXBI: i32 = BINARY + 60;
+    pub const ZEBI: i32 = BINARY + 70;
+    pub const YOBI: i32 = BINARY + 80;
+
+    /// Test if the given prefix belongs to the binary family.
+    pub(crate) fn is_binary(prefix: i32) -> bool {
+        prefix > BINARY / 2
+    }
+
+    /// Calculate the factor of the given prefix raised to the given power.
+    pub(crate) fn factor(prefix: i32, power: i32) -> Rational {
+        if Self::is_binary(prefix) {
+            Rational::new(2u32, 1u32).pow((prefix - BINARY) * power)
+        } else {
+            Rational::new(10u32, 1u32).pow(prefix * power)
+        }
+    }
 
     /// Find the prefix matching the given power and return any extra that comes
     /// along.
+    ///
+    /// The extra is a power of 10 for decimal prefixes, and a power of 2 for
+    /// binary prefixes.
     pub fn find(pow: i32) -> (Self, i32) {
-        let (p, prefix) = match PREFIXES.binary_search_by(|e| e.0.cmp(&pow)) {
-            Ok(n) => PREFIXES[n],
-            Err(n) => PREFIXES[n.saturating_sub(1)],
+        let prefixes = if Self::is_binary(pow) {
+            &BINARY_PREFIXES[..]
+        } else {
+            &PREFIXES[..]
+        };
+
+        let (p, prefix) = match prefixes.binary_search_by(|e| e.0.cmp(&pow)) {
+            Ok(n) => prefixes[n],
+            Err(n) => prefixes[n.saturating_sub(1)],
         };
 
         (prefix, p - pow)
@@ -110,6 +167,14 @@ impl fmt::Display for Prefix {
             Prefix::Atto => 'a'.fmt(f),
             Prefix::Zepto => 'z'.fmt(f),
             Prefix::Yocto => 'y'.fmt(f),
+            Prefix::Kibi => "Ki".fmt(f),
+            Prefix::Mebi => "Mi".fmt(f),
+            Prefix::Gibi => "Gi".fmt(f),
+            Prefix::Tebi => "Ti".fmt(f),
+            Prefix::Pebi => "Pi".fmt(f),
+            Prefix::Exbi => "Ei".fmt(f),
+            Prefix::Zebi => "Zi".fmt(f),
+            Prefix::Yobi => "Yi".fmt(f),
         }
     }
 }
@@ -5,6 +5,7 @@ use codespan_reporting::term::termcolor::StandardStream;
 use syntree::node::Children;
 use syntree::{FlavorDefault, Span, Tree};
 
+use crate::currency::ExchangeRate;
 use crate::db;
 use crate::error::Error;
 use crate::eval::Context;
@@ -15,18 +16,64 @@ use crate::syntax::parser::{Parser, Syntax};
 pub enum Description {
     /// A constant that was used.
     Constant(Box<str>, db::Constant),
+    /// A currency exchange rate that was used.
+    ExchangeRate(ExchangeRate),
+    /// The candidates that were considered when looking up the given query,
+    /// ordered by descending score.
+    Candidates(Box<str>, Vec<db::Candidate>),
 }
 
 /// The options of a query.
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Options {
     pub(crate) describe: bool,
+    pub(crate) auto_units: bool,
+    pub(crate) candidates: bool,
 }
 
 impl Options {
     /// Enable description of a query.
     pub fn describe(self) -> Self {
-        Self { describe: true }
+        Self {
+            describe: true,
+            ..self
+        }
+    }
+
+    /// Describe the candidates that were considered when looking up constants
+    /// in the database.
+    pub fn candidates(self) -> Self {
+        Self {
+            candidates: true,
+            ..self
+        }
+    }
+
+    /// Convert results into their most readable unit unless the query
+    /// contains a cast, like `3 kN * 2 m` giving `6 kJ`. A cast to `auto`
+    /// does this explicitly.
+    ///
+    /// ```
+    /// use anything::{Db, Context, Options};
+    ///
+    /// # fn main() -> anyhow::Result<()> {
+    /// let db = Db::in_memory()?;
+    /// let mut ctx = Context::new();
+    /// let mut descriptions = Vec::new();
+    ///
+    /// let parsed = anything::parse("3 kN * 2 m")?;
+    /// let options = Options::default().auto_units();
+    /// let mut values = anything::query(&parsed, &db, &mut ctx, options, &mut descriptions);
+    ///
+    /// let value = values.next().unwrap()?;
+    /// assert_eq!(value.unit.to_string(), "kJ");
+    /// # Ok(()) }
+    /// ```
+    pub fn auto_units(self) -> Self {
+        Self {
+            auto_units: true,
+            ..self
+        }
     }
 }
 
@@ -58,16 +105,20 @@ pub fn parse(source: &str) -> Result<Parsed<'_>> {
 
 /// Perform a query over the given string and database.
 ///
+/// Variables assigned during the query are stored in `ctx`, which makes them
+/// available to later queries using the same context.
+///
 /// ```
-/// use anything::{Db, Options, parse, query};
+/// use anything::{Context, Db, Options, parse, query};
 ///
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let db = Db::open().unwrap();
 /// let parsed = parse("0.99c")?;
 ///
+/// let mut ctx = Context::new();
 /// let options = Options::default();
 /// let mut descriptions = Vec::new();
-/// let mut values = query(&parsed, &db, options, &mut descriptions);
+/// let mut values = query(&parsed, &db, &mut ctx, options, &mut descriptions);
 ///
 /// assert!(matches!(values.next(), Some(Ok(..))));
 /// # Ok(()) }
@@ -75,11 +126,12 @@ pub fn parse(source: &str) -> Result<Parsed<'_>> {
 pub fn query<'a>(
     parsed: &'a Parsed<'_>,
     db: &'a db::Db,
+    ctx: &'a mut Context,
     options: Options,
     descriptions: &'a mut Vec<Description>,
 ) -> Query<'a> {
     Query {
-        ctx: Context::new(),
+        ctx,
         source: parsed.source,
         db,
         children: parsed.tree.children(),
@@ -94,8 +146,7 @@ pub fn query<'a>(
 ///
 /// See [query].
 pub struct Query<'a> {
-    #[allow(unused)]
-    pub(crate) ctx: Context,
+    pub(crate) ctx: &'a mut Context,
     pub(crate) source: &'a str,
     pub(crate) db: &'a db::Db,
     pub(crate) children: Children<'a, Syntax, FlavorDefault>,
@@ -119,7 +170,33 @@ impl Iterator for Query<'_> {
     type Item = Result<Numeric, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let node = self.children.next()?;
-        Some(crate::eval::eval(self, node, Default::default()))
+        let mut node = self.children.next_node()?;
+
+        // NB: definitions don't produce values.
+        loop {
+            let result = match node.value() {
+                Syntax::FN_DEF => crate::eval::define(self, node),
+                Syntax::UNIT_DEF => crate::eval::define_unit(self, node),
+                _ => break,
+            };
+
+            if let Err(error) = result {
+                return Some(Err(error));
+            }
+
+            node = self.children.next_node()?;
+        }
+
+        let mut result = crate::eval::eval(self, node, Default::default());
+
+        if self.options.auto_units && !node.walk().any(|n| n.value() == Syntax::OP_CAST) {
+            result = result.and_then(|value| crate::eval::auto(*node.span(), value, false));
+        }
+
+        if let Ok(value) = &result {
+            self.ctx.set_last(value);
+        }
+
+        Some(result)
     }
 }
@@ -1,4 +1,5 @@
 use num::{BigInt, BigRational, Signed, ToPrimitive, Zero};
+use std::cmp::Ordering;
 use std::fmt;
 use std::fmt::Write;
 use std::mem;
@@ -13,6 +14,8 @@ pub struct DisplaySpec {
     pub exponent_limit: usize,
     /// Whether or not to show a `...` continuation.
     pub show_continuation: bool,
+    /// How the number is rounded when displayed.
+    pub mode: DisplayMode,
 }
 
 impl Default for DisplaySpec {
@@ -21,10 +24,56 @@ impl Default for DisplaySpec {
             limit: 6,
             exponent_limit: 8,
             show_continuation: true,
+            mode: DisplayMode::Auto,
         }
     }
 }
 
+/// How a rational number is rounded when displayed.
+///
+/// All modes except [DisplayMode::Auto] round to the nearest value, with
+/// halves rounded away from zero, and never show a continuation.
+///
+/// ```
+/// use anything::rational::{DisplayMode, DisplaySpec};
+/// use anything::Rational;
+///
+/// let value = Rational::new(1234567, 100);
+/// let mut spec = DisplaySpec::default();
+///
+/// spec.mode = DisplayMode::Significant(3);
+/// assert_eq!(value.display(&spec).to_string(), "12300");
+///
+/// spec.mode = DisplayMode::Fixed(1);
+/// assert_eq!(value.display(&spec).to_string(), "12345.7");
+///
+/// spec.mode = DisplayMode::Scientific(3);
+/// assert_eq!(value.display(&spec).to_string(), "1.23e4");
+///
+/// spec.mode = DisplayMode::Engineering(3);
+/// assert_eq!(value.display(&spec).to_string(), "12.3e3");
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DisplayMode {
+    /// Show up to [DisplaySpec::limit] digits, followed by a continuation if
+    /// the number is truncated.
+    #[default]
+    Auto,
+    /// Round to the given number of significant figures. Numbers whose
+    /// exponent reaches [DisplaySpec::exponent_limit] are shown in
+    /// scientific notation.
+    Significant(usize),
+    /// Round to the given number of decimal places.
+    Fixed(usize),
+    /// Scientific notation with the given number of significant figures,
+    /// like `1.23e4`.
+    Scientific(usize),
+    /// Engineering notation with the given number of significant figures,
+    /// where the exponent is a multiple of three, like `12.3e3`.
+    Engineering(usize),
+}
+
 /// Perform formatting of a big rational.
 pub struct Display<'a> {
     rational: &'a BigRational,
@@ -131,10 +180,71 @@ impl<'a> Display<'a> {
 
         Ok(())
     }
+
+    /// Format the number rounded to the given number of decimals.
+    fn format_fixed(&self, f: &mut fmt::Formatter<'_>, decimals: usize) -> fmt::Result {
+        write_rounded(f, self.rational, decimals as i32)
+    }
+
+    /// Format the number rounded to the given number of significant figures.
+    fn format_significant(&self, f: &mut fmt::Formatter<'_>, n: usize) -> fmt::Result {
+        let n = n.max(1);
+
+        if self.rational.is_zero() {
+            return write_fixed(f, false, &BigInt::zero(), n - 1);
+        }
+
+        let (m, e) = significand(self.rational, n);
+
+        if e.unsigned_abs() as usize >= self.spec.exponent_limit {
+            return self.format_exponent(f, n, 1);
+        }
+
+        let neg = self.rational.is_negative();
+        let decimals = n as i32 - 1 - e;
+
+        if decimals >= 0 {
+            write_fixed(f, neg, &m, decimals as usize)
+        } else {
+            write_fixed(f, neg, &(m * pow10(-decimals)), 0)
+        }
+    }
+
+    /// Format the number with the given number of significant figures and an
+    /// exponent which is a multiple of `step`.
+    fn format_exponent(&self, f: &mut fmt::Formatter<'_>, n: usize, step: i32) -> fmt::Result {
+        let n = n.max(1);
+
+        if self.rational.is_zero() {
+            write_fixed(f, false, &BigInt::zero(), n - 1)?;
+            return f.write_str("e0");
+        }
+
+        let neg = self.rational.is_negative();
+        let (m, e) = significand(self.rational, n);
+        let exp = e.div_euclid(step) * step;
+        let whole = (e - exp + 1) as usize;
+
+        if n >= whole {
+            write_fixed(f, neg, &m, n - whole)?;
+        } else {
+            write_fixed(f, neg, &(m * pow10((whole - n) as i32)), 0)?;
+        }
+
+        write!(f, "e{exp}")
+    }
 }
 
 impl fmt::Display for Display<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.spec.mode {
+            DisplayMode::Auto => {}
+            DisplayMode::Significant(n) => return self.format_significant(f, n),
+            DisplayMode::Fixed(n) => return self.format_fixed(f, n),
+            DisplayMode::Scientific(n) => return self.format_exponent(f, n, 1),
+            DisplayMode::Engineering(n) => return self.format_exponent(f, n, 3),
+        }
+
         let neg = self.rational.is_negative();
         let mut rem = self.rational.numer().abs();
         let den = self.rational.denom().abs();
@@ -209,6 +319,25 @@ impl fmt::Display for Display<'_> {
     }
 }
 
+/// Format a big rational rounded to a number of decimals, which can be
+/// negative to round to tens, hundreds and so on.
+pub(crate) struct Rounded<'a> {
+    rational: &'a BigRational,
+    decimals: i32,
+}
+
+impl<'a> Rounded<'a> {
+    pub(crate) fn new(rational: &'a BigRational, decimals: i32) -> Self {
+        Self { rational, decimals }
+    }
+}
+
+impl fmt::Display for Rounded<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_rounded(f, self.rational, self.decimals)
+    }
+}
+
 /// Internal helper to keep diving a value and emitting its digits.
 ///
 /// Each emitted value is guaranteed to be smaller than 10.
@@ -240,3 +369,96 @@ fn digits(mut rem: BigInt) -> usize {
 
     count
 }
+
+/// Write an integer as a decimal number with the given number of decimals.
+fn write_fixed(f: &mut fmt::Formatter<'_>, neg: bool, m: &BigInt, decimals: usize) -> fmt::Result {
+    if neg {
+        f.write_char('-')?;
+    }
+
+    let digits = format!("{:0>width$}", m.magnitude(), width = decimals + 1);
+    let (whole, frac) = digits.split_at(digits.len() - decimals);
+    f.write_str(whole)?;
+
+    if !frac.is_empty() {
+        f.write_char('.')?;
+        f.write_str(frac)?;
+    }
+
+    Ok(())
+}
+
+/// Write a value rounded to the given number of decimals, with halves rounded
+/// away from zero.
+fn write_rounded(f: &mut fmt::Formatter<'_>, value: &BigRational, decimals: i32) -> fmt::Result {
+    let m = scaled(value, decimals);
+    let neg = value.is_negative() && !m.is_zero();
+
+    if decimals >= 0 {
+        write_fixed(f, neg, &m, decimals as usize)
+    } else {
+        write_fixed(f, neg, &(m * pow10(-decimals)), 0)
+    }
+}
+
+/// Calculate `10^exp`.
+fn pow10(exp: i32) -> BigInt {
+    num::pow(BigInt::from(10u32), exp.unsigned_abs() as usize)
+}
+
+/// Round the absolute value scaled by `10^exp` to the nearest integer, with
+/// halves rounded away from zero.
+fn scaled(value: &BigRational, exp: i32) -> BigInt {
+    let mut numer = value.numer().abs();
+    let mut denom = value.denom().abs();
+
+    if exp >= 0 {
+        numer *= pow10(exp);
+    } else {
+        denom *= pow10(exp);
+    }
+
+    (numer * 2u32 + &denom) / (denom * 2u32)
+}
+
+/// Compare the absolute value of a rational with `10^exp`.
+fn cmp_pow10(value: &BigRational, exp: i32) -> Ordering {
+    let numer = value.numer().abs();
+    let denom = value.denom().abs();
+
+    if exp >= 0 {
+        numer.cmp(&(denom * pow10(exp)))
+    } else {
+        (numer * pow10(exp)).cmp(&denom)
+    }
+}
+
+/// Get the power of ten of the most significant digit of a non-zero value.
+pub(crate) fn magnitude(value: &BigRational) -> i32 {
+    let mut e = digits(value.numer().abs()) as i32 - digits(value.denom().abs()) as i32;
+
+    while cmp_pow10(value, e) == Ordering::Less {
+        e -= 1;
+    }
+
+    while cmp_pow10(value, e + 1) != Ordering::Less {
+        e += 1;
+    }
+
+    e
+}
+
+/// Get the `n` most significant digits of a non-zero value rounded to the
+/// nearest integer, and the power of ten of the first digit.
+fn significand(value: &BigRational, n: usize) -> (BigInt, i32) {
+    let e = magnitude(value);
+    let n = n as i32;
+    let m = scaled(value, n - 1 - e);
+
+    // Rounding carried into another digit, like `9.99` to `10.0`.
+    if m >= pow10(n) {
+        return (scaled(value, n - 2 - e), e + 1);
+    }
+
+    (m, e)
+}
@@ -1,20 +1,20 @@
 //! Helper module for calculating rational numbers.
 
 use num::traits::Pow;
-use num::{BigInt, BigRational, One, ToPrimitive, Zero};
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
 use serde::{de, ser};
 use std::str::FromStr;
 use std::{fmt, ops};
 use thiserror::Error;
 
 mod display;
-pub use self::display::{Display, DisplaySpec};
+pub use self::display::{Display, DisplayMode, DisplaySpec};
 
 #[cfg(test)]
 mod tests;
 
 /// A rational number.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Rational {
     rational: BigRational,
 }
@@ -52,6 +52,13 @@ impl Rational {
         Some(Self { rational })
     }
 
+    /// Returns the absolute value.
+    pub fn abs(&self) -> Self {
+        Self {
+            rational: self.rational.abs(),
+        }
+    }
+
     /// Returns the reciprocal.
     ///
     /// # Panics
@@ -68,6 +75,18 @@ impl Rational {
         Display::new(&self.rational, spec)
     }
 
+    /// Format this rational number rounded to the given number of decimals,
+    /// which can be negative to round to tens, hundreds and so on.
+    pub(crate) fn display_rounded(&self, decimals: i32) -> display::Rounded<'_> {
+        display::Rounded::new(&self.rational, decimals)
+    }
+
+    /// Get the power of ten of the most significant digit of this number,
+    /// which must not be zero.
+    pub(crate) fn magnitude(&self) -> i32 {
+        display::magnitude(&self.rational)
+    }
+
     /// Raises the `Ratio` to the power of an exponent.
     #[inline]
     pub fn pow(&self, expon: i32) -> Rational
@@ -79,6 +98,75 @@ impl Rational {
         }
     }
 
+    /// Calculate the exact `n`th root of this rational number.
+    ///
+    /// Returns `None` if the root isn't rational, or if an even root of a
+    /// negative number is requested.
+    ///
+    /// ```
+    /// use anything::Rational;
+    ///
+    /// assert_eq!(Rational::new(9, 4).root(2), Some(Rational::new(3, 2)));
+    /// assert_eq!(Rational::new(-27, 1).root(3), Some(Rational::new(-3, 1)));
+    /// assert_eq!(Rational::new(2, 1).root(2), None);
+    /// ```
+    pub fn root(&self, n: u32) -> Option<Rational> {
+        if n == 0 || (n.is_multiple_of(2) && self.rational.is_negative()) {
+            return None;
+        }
+
+        let numer = self.rational.numer().abs();
+        let denom = self.rational.denom();
+
+        let numer_root = numer.nth_root(n);
+        let denom_root = denom.nth_root(n);
+
+        if Pow::pow(&numer_root, n) != numer || &Pow::pow(&denom_root, n) != denom {
+            return None;
+        }
+
+        let numer_root = if self.rational.is_negative() {
+            -numer_root
+        } else {
+            numer_root
+        };
+
+        Some(Self {
+            rational: BigRational::new(numer_root, denom_root),
+        })
+    }
+
+    /// Approximate the `n`th root of this rational number, truncated to the
+    /// given number of decimal digits.
+    ///
+    /// Returns `None` if an even root of a negative number is requested.
+    ///
+    /// ```
+    /// use anything::Rational;
+    ///
+    /// let root = Rational::new(2, 1).root_approx(2, 4);
+    /// assert_eq!(root, Some(Rational::new(14142, 10000)));
+    /// ```
+    pub fn root_approx(&self, n: u32, digits: u32) -> Option<Rational> {
+        if n == 0 || (n.is_multiple_of(2) && self.rational.is_negative()) {
+            return None;
+        }
+
+        let scale = Pow::pow(BigInt::from(10u32), digits);
+        let numer = self.rational.numer().abs() * Pow::pow(&scale, n);
+        let root = (numer / self.rational.denom()).nth_root(n);
+
+        let root = if self.rational.is_negative() {
+            -root
+        } else {
+            root
+        };
+
+        Some(Self {
+            rational: BigRational::new(root, scale),
+        })
+    }
+
     /// Round the current rational to it's closest whole number.
     pub fn round(&self) -> Rational {
         Self {
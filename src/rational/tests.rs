@@ -1,4 +1,4 @@
-use crate::rational::DisplaySpec;
+use crate::rational::{DisplayMode, DisplaySpec};
 use crate::Rational;
 
 #[test]
@@ -51,12 +51,14 @@ fn test_display() {
         limit: 8,
         exponent_limit: 6,
         show_continuation: true,
+        mode: DisplayMode::Auto,
     };
 
     let extended = DisplaySpec {
         limit: 40,
         exponent_limit: 6,
         show_continuation: true,
+        mode: DisplayMode::Auto,
     };
 
     let s = Rational::new(1u32, 3u32).display(&basic).to_string();
@@ -76,6 +78,44 @@ fn test_display() {
     assert_eq!(s, "7.066521986454909362816626182915140226065…e-19");
 }
 
+#[test]
+fn test_display_modes() {
+    let display = |r: &Rational, mode: DisplayMode| {
+        let spec = DisplaySpec {
+            mode,
+            ..DisplaySpec::default()
+        };
+
+        r.display(&spec).to_string()
+    };
+
+    let r = Rational::new(-2i32, 3u32);
+    assert_eq!(display(&r, DisplayMode::Significant(3)), "-0.667");
+    assert_eq!(display(&r, DisplayMode::Fixed(0)), "-1");
+    assert_eq!(display(&r, DisplayMode::Fixed(4)), "-0.6667");
+    assert_eq!(display(&r, DisplayMode::Scientific(2)), "-6.7e-1");
+    assert_eq!(display(&r, DisplayMode::Engineering(2)), "-670e-3");
+
+    let r = Rational::new(9996u32, 10u32);
+    assert_eq!(display(&r, DisplayMode::Significant(3)), "1000");
+    assert_eq!(display(&r, DisplayMode::Scientific(3)), "1.00e3");
+    assert_eq!(display(&r, DisplayMode::Engineering(3)), "1.00e3");
+    assert_eq!(display(&r, DisplayMode::Engineering(1)), "1e3");
+
+    let r = Rational::new(667430u32, 10000000000000000u64);
+    assert_eq!(display(&r, DisplayMode::Significant(4)), "6.674e-11");
+    assert_eq!(display(&r, DisplayMode::Engineering(4)), "66.74e-12");
+
+    let r = Rational::new(123456u32, 1u32);
+    assert_eq!(display(&r, DisplayMode::Significant(2)), "120000");
+    assert_eq!(display(&r, DisplayMode::Engineering(2)), "120e3");
+    assert_eq!(display(&r, DisplayMode::Fixed(2)), "123456.00");
+
+    let r = Rational::new(0u32, 1u32);
+    assert_eq!(display(&r, DisplayMode::Significant(3)), "0.00");
+    assert_eq!(display(&r, DisplayMode::Scientific(1)), "0e0");
+}
+
 #[test]
 fn deserialize() {
     let r = Rational::new(1u32, 100u32);
@@ -22,7 +22,33 @@ pub fn root(p: &mut Parser<'_>) -> Result<()> {
                 p.skip(skip)?;
                 break;
             }
-            OPEN_BRACE | OPEN_PAREN | WORD | NUMBER => {
+            SEMICOLON => {
+                p.skip(skip)?;
+                p.bump()?;
+                skip = p.count_skip();
+            }
+            _ if is_assign(p, skip) => {
+                if let Some(s) = assign(p, skip)? {
+                    skip = s;
+                } else {
+                    p.error_node_at(&c)?;
+                }
+            }
+            _ if is_unit_def(p, skip) => {
+                if let Some(s) = unit_def(p, skip)? {
+                    skip = s;
+                } else {
+                    p.error_node_at(&c)?;
+                }
+            }
+            _ if is_fn_def(p, skip) => {
+                if let Some(s) = fn_def(p, skip)? {
+                    skip = s;
+                } else {
+                    p.error_node_at(&c)?;
+                }
+            }
+            OPEN_BRACE | OPEN_PAREN | WORD | NUMBER | DATE => {
                 if let Some(s) = operation(p, skip)? {
                     skip = s;
                 } else {
@@ -45,6 +71,196 @@ pub fn root(p: &mut Parser<'_>) -> Result<()> {
     Ok(())
 }
 
+/// Test if the parser is positioned at an assignment.
+fn is_assign(p: &mut Parser<'_>, skip: Skip) -> bool {
+    match p.nth(skip, 0) {
+        LET => true,
+        WORD => match p.nth(skip, 1) {
+            EQ => true,
+            WHITESPACE => p.nth(skip, 2) == EQ,
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Parse an assignment like `let name = <operation>` or `name = <operation>`.
+fn assign(p: &mut Parser<'_>, skip: Skip) -> Result<Option<Skip>> {
+    p.skip(skip)?;
+    let c = p.checkpoint()?;
+
+    if let LET = p.nth(Skip::ZERO, 0) {
+        p.bump()?;
+        let skip = p.count_skip();
+
+        if p.nth(skip, 0) != WORD {
+            return Ok(None);
+        }
+
+        p.skip(skip)?;
+    }
+
+    p.bump_node(WORD)?;
+
+    let skip = p.count_skip();
+
+    if p.nth(skip, 0) != EQ {
+        return Ok(None);
+    }
+
+    p.skip(skip)?;
+    p.bump()?;
+
+    let skip = p.count_skip();
+
+    let skip = match operation(p, skip)? {
+        Some(skip) => skip,
+        None => return Ok(None),
+    };
+
+    p.close_at(&c, ASSIGN)?;
+    Ok(Some(skip))
+}
+
+/// Test if the parser is positioned at a unit definition.
+fn is_unit_def(p: &mut Parser<'_>, skip: Skip) -> bool {
+    if p.nth(skip, 0) != WORD || p.nth_text(skip, 0) != "unit" {
+        return false;
+    }
+
+    if p.nth(skip, 1) != WHITESPACE || p.nth(skip, 2) != WORD {
+        return false;
+    }
+
+    match p.nth(skip, 3) {
+        EQ => true,
+        WHITESPACE => p.nth(skip, 4) == EQ,
+        _ => false,
+    }
+}
+
+/// Parse a unit definition like `unit smoot = <operation>`.
+fn unit_def(p: &mut Parser<'_>, skip: Skip) -> Result<Option<Skip>> {
+    p.skip(skip)?;
+    let c = p.checkpoint()?;
+
+    // The `unit` keyword.
+    p.bump()?;
+
+    let skip = p.count_skip();
+    p.skip(skip)?;
+    p.bump_node(WORD)?;
+
+    let skip = p.count_skip();
+
+    if !p.eat(skip, &[EQ])? {
+        return Ok(None);
+    }
+
+    let skip = p.count_skip();
+
+    let skip = match operation(p, skip)? {
+        Some(skip) => skip,
+        None => return Ok(None),
+    };
+
+    p.close_at(&c, UNIT_DEF)?;
+    Ok(Some(skip))
+}
+
+/// Test if the parser is positioned at a function definition.
+fn is_fn_def(p: &mut Parser<'_>, skip: Skip) -> bool {
+    if p.nth(skip, 0) != WORD || p.nth(skip, 1) != OPEN_PAREN {
+        return false;
+    }
+
+    let mut n = 2;
+
+    loop {
+        match p.nth(skip, n) {
+            CLOSE_PAREN => break,
+            OPEN_PAREN | EOF => return false,
+            _ => n += 1,
+        }
+    }
+
+    match p.nth(skip, n + 1) {
+        EQ => true,
+        WHITESPACE => p.nth(skip, n + 2) == EQ,
+        _ => false,
+    }
+}
+
+/// Parse a function definition like `name(a, b: unit) = <operation>`.
+fn fn_def(p: &mut Parser<'_>, skip: Skip) -> Result<Option<Skip>> {
+    p.skip(skip)?;
+    let c = p.checkpoint()?;
+
+    p.bump_node(FN_NAME)?;
+    p.bump()?;
+
+    let params = p.checkpoint()?;
+
+    let skip = loop {
+        let skip = p.count_skip();
+
+        match p.nth(skip, 0) {
+            CLOSE_PAREN => break skip,
+            WORD => {
+                p.skip(skip)?;
+                let param = p.checkpoint()?;
+                p.bump_node(WORD)?;
+
+                let skip = p.count_skip();
+
+                let skip = if p.nth(skip, 0) == COLON {
+                    p.skip(skip)?;
+                    p.bump()?;
+
+                    let skip = p.count_skip();
+
+                    if unit(p, skip)?.is_none() {
+                        return Ok(None);
+                    }
+
+                    p.count_skip()
+                } else {
+                    skip
+                };
+
+                p.close_at(&param, FN_PARAM)?;
+
+                if !p.eat(skip, &[COMMA])? {
+                    break skip;
+                }
+            }
+            _ => return Ok(None),
+        }
+    };
+
+    p.close_at(&params, FN_PARAMS)?;
+
+    if !p.eat(skip, &[CLOSE_PAREN])? {
+        return Ok(None);
+    }
+
+    let skip = p.count_skip();
+
+    if !p.eat(skip, &[EQ])? {
+        return Ok(None);
+    }
+
+    let skip = p.count_skip();
+
+    let skip = match operation(p, skip)? {
+        Some(skip) => skip,
+        None => return Ok(None),
+    };
+
+    p.close_at(&c, FN_DEF)?;
+    Ok(Some(skip))
+}
+
 fn call_arguments(p: &mut Parser<'_>) -> Result<bool> {
     let c = p.checkpoint()?;
 
@@ -123,6 +339,13 @@ fn value(p: &mut Parser<'_>, skip: Skip) -> Result<Option<Checkpoint<PointerUsiz
                 return Ok(Some(start));
             }
 
+            if let (DOT, WORD) = (p.nth(Skip::ZERO, 0), p.nth(Skip::ZERO, 1)) {
+                p.bump()?;
+                p.bump_node(WORD)?;
+                p.close_at(&c, ATTRIBUTE)?;
+                return Ok(Some(start));
+            }
+
             let mut skip = p.count_skip();
             let mut is_sentence = false;
 
@@ -144,7 +367,29 @@ fn value(p: &mut Parser<'_>, skip: Skip) -> Result<Option<Checkpoint<PointerUsiz
             let c = p.checkpoint()?;
             p.bump()?;
 
-            let skip = p.count_skip();
+            let mut skip = p.count_skip();
+            let mut value = NUMBER;
+
+            // A number with an uncertainty like `9.81 ± 0.02`.
+            if p.nth(skip, 0) == PLUS_MINUS {
+                p.skip(skip)?;
+                p.bump()?;
+
+                let skip = p.count_skip();
+
+                if !p.eat(skip, &[NUMBER])? {
+                    return Ok(None);
+                }
+
+                // A relative uncertainty like `10 ± 5%`.
+                let skip = p.count_skip();
+                p.eat(skip, &[PERCENTAGE])?;
+
+                p.close_at(&c, UNCERTAIN)?;
+                value = UNCERTAIN;
+            }
+
+            skip = p.count_skip();
 
             let kind = match p.nth(skip, 0) {
                 PERCENTAGE => {
@@ -156,12 +401,48 @@ fn value(p: &mut Parser<'_>, skip: Skip) -> Result<Option<Checkpoint<PointerUsiz
                     if unit(p, skip)?.is_some() {
                         WITH_UNIT
                     } else {
-                        NUMBER
+                        value
                     }
                 }
             };
 
-            p.close_at(&c, kind)?;
+            if kind != UNCERTAIN {
+                p.close_at(&c, kind)?;
+            }
+
+            // Mixed units like `5ft 11in`, where each number is followed by a
+            // unit.
+            if kind == WITH_UNIT {
+                let mut is_mixed = false;
+
+                loop {
+                    let skip = p.count_skip();
+
+                    if p.nth(skip, 0) != NUMBER || !is_unit_next(p, skip) {
+                        break;
+                    }
+
+                    p.skip(skip)?;
+                    let part = p.checkpoint()?;
+                    p.bump()?;
+
+                    let skip = p.count_skip();
+                    unit(p, skip)?;
+                    p.close_at(&part, WITH_UNIT)?;
+                    is_mixed = true;
+                }
+
+                if is_mixed {
+                    p.close_at(&c, MIXED)?;
+                }
+            }
+
+            Ok(Some(c))
+        }
+        DATE => {
+            p.skip(skip)?;
+            let c = p.checkpoint()?;
+            p.bump_node(DATE)?;
             Ok(Some(c))
         }
         OPEN_PAREN => {
@@ -169,6 +450,8 @@ fn value(p: &mut Parser<'_>, skip: Skip) -> Result<Option<Checkpoint<PointerUsiz
             let c = p.checkpoint()?;
             p.bump()?;
 
+            let skip = p.count_skip();
+
             let skip = match operation(p, skip)? {
                 Some(skip) => skip,
                 None => return Ok(None),
@@ -202,7 +485,10 @@ pub fn operation(p: &mut Parser<'_>, mut skip: Skip) -> Result<Option<Skip>> {
 
         let (priority, operator, extra, cur_skip) = match op(p) {
             Some(out) => out,
-            None => break,
+            None => {
+                skip = p.count_skip();
+                break;
+            }
         };
 
         if std::mem::take(&mut first) {
@@ -246,7 +532,7 @@ pub fn operation(p: &mut Parser<'_>, mut skip: Skip) -> Result<Option<Skip>> {
     ) -> Result<Option<Checkpoint<PointerUsize>>> {
         let c = if is_unit {
             p.skip(skip)?;
-            unit(p, Skip::ZERO)?
+            mixed_unit(p)?
         } else {
             value(p, skip)?
         };
@@ -272,6 +558,41 @@ pub fn operation(p: &mut Parser<'_>, mut skip: Skip) -> Result<Option<Skip>> {
     }
 }
 
+/// Test if the number at the given skip is directly followed by a word,
+/// with or without whitespace in between.
+fn is_unit_next(p: &mut Parser<'_>, skip: Skip) -> bool {
+    match p.nth(skip, 1) {
+        WORD => true,
+        WHITESPACE => p.nth(skip, 2) == WORD,
+        _ => false,
+    }
+}
+
+/// Parse a unit which is optionally followed by more units, like `ft+in`.
+fn mixed_unit(p: &mut Parser<'_>) -> Result<Option<Checkpoint<PointerUsize>>> {
+    let Some(c) = unit(p, Skip::ZERO)? else {
+        return Ok(None);
+    };
+
+    let mut is_mixed = false;
+
+    while p.nth(Skip::ZERO, 0) == PLUS && p.nth(Skip::ZERO, 1) == WORD {
+        p.bump()?;
+
+        if unit(p, Skip::ZERO)?.is_none() {
+            return Ok(None);
+        }
+
+        is_mixed = true;
+    }
+
+    if is_mixed {
+        p.close_at(&c, MIXED_UNIT)?;
+    }
+
+    Ok(Some(c))
+}
+
 /// Parse a unit.
 pub fn unit(p: &mut Parser<'_>, mut skip: Skip) -> Result<Option<Checkpoint<PointerUsize>>> {
     let mut c = None;
@@ -279,7 +600,7 @@ pub fn unit(p: &mut Parser<'_>, mut skip: Skip) -> Result<Option<Checkpoint<Poin
     'outer: loop {
         // lead
         let kind = match p.nth(skip, 0) {
-            NUMBER => NUMBER,
+            NUMBER if c.is_none() => NUMBER,
             WORD => WORD,
             _ => break,
         };
@@ -82,10 +82,58 @@ impl<'a> Lexer<'a> {
         count
     }
 
+    /// Consume a date like `2026-10-18`, optionally followed by a time of day
+    /// like `T09:30:00.5` and a UTC offset like `Z` or `+02:00`.
+    fn consume_date(&mut self) -> bool {
+        let Some(rest) = self.source.get(self.pos..) else {
+            return false;
+        };
+
+        if !matches(rest, "dddd-dd-dd") || matches(&rest[10..], "d") {
+            return false;
+        }
+
+        let mut len = 10;
+
+        if matches(&rest[len..], "Tdd:dd") {
+            len += 6;
+
+            if matches(&rest[len..], ":dd") {
+                len += 3;
+
+                if matches(&rest[len..], ".d") {
+                    len += 1;
+                    len += rest[len..].bytes().take_while(u8::is_ascii_digit).count();
+                }
+            }
+
+            if matches(&rest[len..], "Z") {
+                len += 1;
+            } else if matches(&rest[len..], "+dd:dd") || matches(&rest[len..], "-dd:dd") {
+                len += 6;
+            }
+        }
+
+        self.pos += len;
+        return true;
+
+        /// Test if the string starts with the given pattern, where `d` matches
+        /// any ASCII digit.
+        fn matches(s: &str, pattern: &str) -> bool {
+            s.len() >= pattern.len()
+                && s.bytes().zip(pattern.bytes()).all(|(c, p)| match p {
+                    b'd' => c.is_ascii_digit(),
+                    p => c == p,
+                })
+        }
+    }
+
     fn consume_word(&mut self) -> usize {
         let mut count = 0;
 
-        while let Some('a'..='z' | 'A'..='Z' | '0'..='9' | '°' | '\'') = self.peek() {
+        while let Some('a'..='z' | 'A'..='Z' | '0'..='9' | '_' | '°' | '′' | '″' | '€' | '\'') =
+            self.peek()
+        {
             count += 1;
             self.step();
         }
@@ -167,7 +215,9 @@ impl Iterator for Lexer<'_> {
             '.' => {
                 self.step();
 
-                if self.consume_number(true) == 0 {
+                if matches!(self.peek(), Some('a'..='z' | 'A'..='Z' | '_')) {
+                    DOT
+                } else if self.consume_number(true) == 0 {
                     ERROR
                 } else {
                     NUMBER
@@ -177,9 +227,25 @@ impl Iterator for Lexer<'_> {
                 self.step();
                 COMMA
             }
+            ';' => {
+                self.step();
+                SEMICOLON
+            }
+            '=' => {
+                self.step();
+                EQ
+            }
+            ':' => {
+                self.step();
+                COLON
+            }
             '0'..='9' => {
-                self.consume_number(false);
-                NUMBER
+                if self.consume_date() {
+                    DATE
+                } else {
+                    self.consume_number(false);
+                    NUMBER
+                }
             }
             '*' => {
                 self.step();
@@ -213,6 +279,10 @@ impl Iterator for Lexer<'_> {
                     DASH
                 }
             }
+            '±' => {
+                self.step();
+                PLUS_MINUS
+            }
             '^' => {
                 self.step();
                 CARET
@@ -233,6 +303,7 @@ impl Iterator for Lexer<'_> {
                 if self.consume_word() > 0 {
                     match &self.source[start..self.pos] {
                         "to" => TO,
+                        "let" => LET,
                         _ => WORD,
                     }
                 } else {
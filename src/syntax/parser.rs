@@ -33,10 +33,20 @@ pub enum Syntax {
     PLUS,
     /// `-`.
     DASH,
+    /// `±`.
+    PLUS_MINUS,
     /// `^`.
     CARET,
     /// `,`.
     COMMA,
+    /// `;`.
+    SEMICOLON,
+    /// `=`.
+    EQ,
+    /// `:`.
+    COLON,
+    /// `.` between an entity and its attribute.
+    DOT,
     /// Open delimiter.
     OPEN_PAREN,
     /// Close delimiter.
@@ -47,16 +57,28 @@ pub enum Syntax {
     CLOSE_BRACE,
     /// The `to` keyword.
     TO,
+    /// The `let` keyword.
+    LET,
     /// A word.
     WORD,
     /// A sentence of words.
     SENTENCE,
+    /// An attribute of an entity, like `mars.mass`.
+    ATTRIBUTE,
     /// A number.
     NUMBER,
+    /// A date or timestamp, like `2026-10-18` or `2026-10-18T09:30:00Z`.
+    DATE,
     /// A number with a unit.
     WITH_UNIT,
+    /// A number with an uncertainty, like `9.81 ± 0.02`.
+    UNCERTAIN,
     /// A unit.
     UNIT,
+    /// A value made up of several numbers with units, like `5ft 11in`.
+    MIXED,
+    /// Several units to break a value down into, like `ft+in`.
+    MIXED_UNIT,
 
     /// The name of the function being called.
     FN_NAME,
@@ -64,10 +86,21 @@ pub enum Syntax {
     FN_ARGUMENTS,
     /// A function call.
     FN_CALL,
+    /// A single function parameter, optionally annotated with a unit.
+    FN_PARAM,
+    /// The parameters of a function being defined.
+    FN_PARAMS,
+    /// A function definition.
+    FN_DEF,
+    /// A unit definition.
+    UNIT_DEF,
 
     /// A percentage expression.
     PERCENTAGE,
 
+    /// A variable assignment.
+    ASSIGN,
+
     /// Cast values.
     OP_CAST,
     /// Add values.
@@ -97,6 +130,8 @@ use Syntax::*;
 
 /// A parser.
 pub struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
     lexer: Lexer<'a>,
     builder: Builder<Syntax, FlavorDefault>,
     buf: VecDeque<Token>,
@@ -106,6 +141,8 @@ impl<'a> Parser<'a> {
     /// Construct a new parser.
     pub fn new(source: &'a str) -> Parser<'a> {
         Self {
+            source,
+            pos: 0,
             lexer: Lexer::new(source),
             builder: Builder::new_with(),
             buf: VecDeque::new(),
@@ -129,7 +166,7 @@ impl<'a> Parser<'a> {
 
     pub(crate) fn eat(&mut self, skip: Skip, expected: &[Syntax]) -> Result<bool> {
         for (n, k) in expected.iter().enumerate() {
-            match self.get(n) {
+            match self.get(skip.0 + n) {
                 Some(t) if t.kind == *k => {}
                 _ => return Ok(false),
             }
@@ -219,10 +256,24 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Get the text of the token at the given lookahead.
+    pub(crate) fn nth_text(&mut self, skip: Skip, n: usize) -> &'a str {
+        let n = skip.0 + n;
+        self.fill(n);
+
+        let start = self.pos + self.buf.iter().take(n).map(|t| t.len).sum::<usize>();
+
+        match self.buf.get(n) {
+            Some(t) => &self.source[start..start + t.len],
+            None => "",
+        }
+    }
+
     pub(crate) fn bump(&mut self) -> Result<()> {
         if let Some(t) = self.get(0) {
             self.builder.token(t.kind, t.len)?;
             self.buf.pop_front();
+            self.pos += t.len;
         }
 
         Ok(())
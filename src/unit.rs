@@ -7,9 +7,10 @@ use serde::{Deserialize, Serialize};
 use std::cmp;
 use std::fmt;
 use std::hash;
+use std::sync::Arc;
 
 /// A base unit.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Unit {
     /// A custom derived unit.
     ///
@@ -31,6 +32,20 @@ pub enum Unit {
     Mole,
     /// A byte base unit as `B`.
     Byte,
+    /// Radian base unit as `rad`.
+    ///
+    /// Angles are dimensionless, but tracked as their own unit so that they
+    /// can be converted between.
+    Radian,
+    /// Euro base unit as `EUR`.
+    ///
+    /// Other currencies are defined at runtime in terms of the euro using
+    /// exchange rates, see [Rates][crate::Rates].
+    Euro,
+    /// A unit defined at runtime.
+    ///
+    /// See [UnitRegistry][crate::UnitRegistry].
+    Custom(Custom),
 }
 
 impl Unit {
@@ -46,14 +61,21 @@ impl Unit {
     ///
     /// assert_eq!(powers.get(Unit::Second), Some(-1));
     /// ```
-    pub fn powers(self, powers: &mut Powers, power: i32) -> bool {
+    pub fn powers(&self, powers: &mut Powers, power: i32) -> bool {
         match self {
             Unit::Derived(derived) => {
                 (derived.vtable.powers)(powers, power);
                 true
             }
+            Unit::Custom(custom) => {
+                for (unit, p) in custom.def.powers.iter() {
+                    powers.insert(unit.clone(), p * power);
+                }
+
+                true
+            }
             unit => {
-                powers.insert(unit, power);
+                powers.insert(unit.clone(), power);
                 false
             }
         }
@@ -61,10 +83,10 @@ impl Unit {
 
     /// Access conversion functions for the given unit.
     pub fn conversion(&self) -> Option<Conversion> {
-        if let Unit::Derived(d) = self {
-            d.vtable.conversion
-        } else {
-            None
+        match self {
+            Unit::Derived(d) => d.vtable.conversion,
+            Unit::Custom(c) => c.def.conversion,
+            _ => None,
         }
     }
 
@@ -87,7 +109,10 @@ impl Unit {
             Unit::Mole => "mol".fmt(f),
             Unit::Candela => "cd".fmt(f),
             Unit::Byte => 'B'.fmt(f),
+            Unit::Radian => "rad".fmt(f),
+            Unit::Euro => "EUR".fmt(f),
             Unit::Derived(derived) => (derived.vtable.format)(f, pluralize),
+            Unit::Custom(custom) => custom.def.name.fmt(f),
         }
     }
 
@@ -167,8 +192,20 @@ pub enum Conversion {
     Methods(ConversionMethods),
     Factor(ConversionFraction),
     Offset(ConversionFraction),
+    /// A conversion factor which is a multiple of `π`, like for angles.
+    ///
+    /// Since `π` is irrational, conversions which don't cancel out `π` are
+    /// approximate. See [PI].
+    Pi(ConversionFraction),
 }
 
+/// The rational approximation of `π` used in conversions, correct to 38
+/// decimal digits.
+pub const PI: ConversionFraction = ConversionFraction {
+    numer: 314159265358979323846264338327950288420,
+    denom: 100000000000000000000000000000000000000,
+};
+
 /// The vtable for a derived unit.
 pub struct DerivedVtable {
     /// Populate base powers.
@@ -262,6 +299,93 @@ impl<'de> de::Deserialize<'de> for Derived {
     }
 }
 
+/// The definition of a [Custom] unit.
+pub(crate) struct CustomDef {
+    /// The name of the unit.
+    pub(crate) name: Box<str>,
+    /// The base powers of the unit.
+    pub(crate) powers: Box<[(Unit, i32)]>,
+    /// How to convert the unit into its base powers.
+    pub(crate) conversion: Option<Conversion>,
+}
+
+/// A unit defined at runtime.
+///
+/// See [UnitRegistry][crate::UnitRegistry].
+#[derive(Clone)]
+pub struct Custom {
+    /// The unique identifier for this custom unit.
+    pub(crate) id: u32,
+    /// The definition of the unit.
+    pub(crate) def: Arc<CustomDef>,
+}
+
+impl Custom {
+    /// Get the name of the custom unit.
+    pub fn name(&self) -> &str {
+        &self.def.name
+    }
+}
+
+impl fmt::Debug for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Custom")
+            .field("name", &self.def.name)
+            .field("id", &format_args!("{:#x}", self.id))
+            .finish()
+    }
+}
+
+impl cmp::PartialEq for Custom {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.id.eq(&other.id)
+    }
+}
+
+impl cmp::PartialOrd for Custom {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for Custom {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl cmp::Eq for Custom {}
+
+impl hash::Hash for Custom {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        state.write_u32(self.id);
+    }
+}
+
+impl Serialize for Custom {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.def.name.serialize(serializer)
+    }
+}
+
+impl<'de> de::Deserialize<'de> for Custom {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let name = Box::<str>::deserialize(deserializer)?;
+
+        Err(<D::Error as de::Error>::custom(format!(
+            "custom unit `{name}` cannot be deserialized"
+        )))
+    }
+}
+
 fn pow_into_char(pow: u32) -> char {
     match pow {
         0 => '⁰',
@@ -1,13 +1,15 @@
 use crate::unit::Unit;
+use crate::unit_registry::UnitRegistry;
 
 /// Helper to parse collection of units from a string.
 pub struct UnitParser<'a> {
     source: &'a str,
+    registry: &'a UnitRegistry,
 }
 
 impl<'a> UnitParser<'a> {
-    pub fn new(source: &'a str) -> Self {
-        Self { source }
+    pub fn new(source: &'a str, registry: &'a UnitRegistry) -> Self {
+        Self { source, registry }
     }
 
     /// Parse the next unit and base.
@@ -16,12 +18,26 @@ impl<'a> UnitParser<'a> {
             return Ok(None);
         }
 
+        // NB: A custom unit which matches exactly takes precedence, since the
+        // generated parser might otherwise partially match a built-in unit
+        // like `s` in `smoot`. Built-in units themselves can't be redefined.
+        if let Some(unit) = self.registry.get(self.source) {
+            self.source = "";
+            return Ok(Some((0, unit)));
+        }
+
         match crate::generated::unit::parse(self.source) {
             Some((remainder, prefix, unit)) => {
                 self.source = remainder;
                 Ok(Some((prefix, unit)))
             }
-            None => Err(self.source),
+            None => match self.registry.find_prefix(self.source) {
+                Some((remainder, unit)) => {
+                    self.source = remainder;
+                    Ok(Some((0, unit)))
+                }
+                None => Err(self.source),
+            },
         }
     }
 }
@@ -0,0 +1,268 @@
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use hashbrown::HashMap;
+use num::{Signed, ToPrimitive, Zero};
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::compound::Compound;
+use crate::numeric::Numeric;
+use crate::rational::Rational;
+use crate::unit::{Conversion, ConversionFraction, Custom, CustomDef, Unit, PI};
+
+/// Identifier to use for the next custom unit.
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+/// Error raised when defining a unit.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum UnitDefinitionError {
+    /// The conversion is zero or negative.
+    #[error("conversion must be positive")]
+    NonPositive,
+    /// The conversion doesn't fit in a conversion fraction.
+    #[error("conversion is too large")]
+    TooLarge,
+    /// The unit couldn't be converted into base units.
+    #[error("`{unit}` cannot be converted into base units")]
+    Conversion {
+        /// The unit that couldn't be converted.
+        unit: Compound,
+    },
+    /// The name is already a built-in unit, which can't be redefined.
+    #[error("`{name}` is a built-in unit")]
+    BuiltIn {
+        /// The name of the built-in unit.
+        name: Box<str>,
+    },
+    /// Offset units must be defined in terms of a single base unit.
+    #[error("offset unit must be defined in terms of a single base unit, but got `{unit}`")]
+    IllegalOffset {
+        /// The unit which was used.
+        unit: Compound,
+    },
+}
+
+#[derive(Deserialize)]
+struct Doc<'a> {
+    #[serde(default, borrow)]
+    units: Vec<Definition<'a>>,
+}
+
+#[derive(Deserialize)]
+struct Definition<'a> {
+    #[serde(borrow)]
+    names: Vec<Cow<'a, str>>,
+    #[serde(default, borrow)]
+    value: Option<Cow<'a, str>>,
+    #[serde(default, borrow)]
+    offset: Option<Cow<'a, str>>,
+    #[serde(default, borrow)]
+    unit: Option<Cow<'a, str>>,
+}
+
+/// A registry of units which are defined at runtime.
+///
+/// Units are either defined as a multiple of another unit, like
+/// `unit smoot = 1.7018 m`, or with an offset in a single base unit. Units
+/// can also be loaded from a TOML document:
+///
+/// ```
+/// use anything::UnitRegistry;
+///
+/// # fn main() -> anyhow::Result<()> {
+/// let mut registry = UnitRegistry::new();
+///
+/// registry.load_toml(r#"
+/// [[units]]
+/// names = ["smoot", "smoots"]
+/// value = "1.7018"
+/// unit = "m"
+///
+/// [[units]]
+/// names = ["degC"]
+/// offset = "273.15"
+/// unit = "K"
+/// "#)?;
+///
+/// assert!(registry.get("smoots").is_some());
+/// # Ok(()) }
+/// ```
+#[derive(Default)]
+pub struct UnitRegistry {
+    names: HashMap<Box<str>, Unit>,
+}
+
+impl UnitRegistry {
+    /// Construct a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the unit with the given name.
+    pub fn get(&self, name: &str) -> Option<Unit> {
+        self.names.get(name).cloned()
+    }
+
+    /// Find the longest defined unit which is a prefix of the given string and
+    /// return it together with the remainder.
+    pub(crate) fn find_prefix<'a>(&self, s: &'a str) -> Option<(&'a str, Unit)> {
+        let (name, unit) = self
+            .names
+            .iter()
+            .filter(|(name, _)| s.starts_with(name.as_ref()))
+            .max_by_key(|(name, _)| name.len())?;
+
+        Some((&s[name.len()..], unit.clone()))
+    }
+
+    /// Define a unit with the given names as a multiple of the given value.
+    ///
+    /// The first name is used when displaying the unit.
+    pub fn define(&mut self, names: &[&str], value: Numeric) -> Result<Unit, UnitDefinitionError> {
+        let (_, powers) = value.unit.base_units();
+        let base = powers
+            .iter()
+            .map(|(u, p)| (u, (p, 0)))
+            .collect::<Compound>();
+
+        let mut factor = value.value;
+
+        if !matches!(base.factor(&value.unit, &mut factor), Ok(true)) {
+            return Err(UnitDefinitionError::Conversion { unit: value.unit });
+        }
+
+        // NB: units defined in terms of angles keep being multiples of `π`, so
+        // that conversions between them remain exact.
+        let conversion = match value.unit.pi_power() {
+            0 => Conversion::Factor(fraction(&factor)?),
+            1 => {
+                factor /= Rational::new(PI.numer, PI.denom);
+                Conversion::Pi(fraction(&factor)?)
+            }
+            _ => return Err(UnitDefinitionError::Conversion { unit: value.unit }),
+        };
+
+        self.insert(names, powers.iter().collect(), conversion)
+    }
+
+    /// Define a unit with the given names, which is offset from the given base
+    /// unit.
+    ///
+    /// The first name is used when displaying the unit.
+    pub fn define_offset(
+        &mut self,
+        names: &[&str],
+        offset: Rational,
+        unit: Compound,
+    ) -> Result<Unit, UnitDefinitionError> {
+        let (_, powers) = unit.base_units();
+
+        let base = match powers.iter().next() {
+            Some((base, 1)) if unit == Compound::from_iter([(base.clone(), (1, 0))]) => base,
+            _ => return Err(UnitDefinitionError::IllegalOffset { unit }),
+        };
+
+        let conversion = Conversion::Offset(fraction(&offset)?);
+        self.insert(names, vec![(base, 1)], conversion)
+    }
+
+    /// Load unit definitions from the given TOML document.
+    pub fn load_toml(&mut self, source: &str) -> Result<()> {
+        let doc: Doc<'_> = toml::from_str(source)?;
+
+        for d in doc.units {
+            let names = d.names.iter().map(|n| n.as_ref()).collect::<Vec<_>>();
+
+            let Some(first) = names.first() else {
+                return Err(anyhow!("unit definition is missing names"));
+            };
+
+            let unit = match &d.unit {
+                Some(unit) => str::parse::<Compound>(unit)
+                    .with_context(|| anyhow!("{first}: bad unit `{unit}`"))?,
+                None => Compound::empty(),
+            };
+
+            let result = match (&d.value, &d.offset) {
+                (value, None) => {
+                    let value = match value {
+                        Some(value) => str::parse::<Rational>(value)
+                            .with_context(|| anyhow!("{first}: bad value `{value}`"))?,
+                        None => Rational::new(1, 1),
+                    };
+
+                    self.define(&names, Numeric::new(value, unit))
+                }
+                (None, Some(offset)) => {
+                    let offset = str::parse::<Rational>(offset)
+                        .with_context(|| anyhow!("{first}: bad offset `{offset}`"))?;
+                    self.define_offset(&names, offset, unit)
+                }
+                (Some(..), Some(..)) => {
+                    return Err(anyhow!("{first}: both `value` and `offset` specified"));
+                }
+            };
+
+            result.with_context(|| anyhow!("{first}: bad unit definition"))?;
+        }
+
+        Ok(())
+    }
+
+    fn insert(
+        &mut self,
+        names: &[&str],
+        powers: Vec<(Unit, i32)>,
+        conversion: Conversion,
+    ) -> Result<Unit, UnitDefinitionError> {
+        // NB: built-in units are parsed after custom units, so redefining one
+        // would silently change what it means.
+        if let Some(name) = names.iter().find(|name| is_built_in(name)) {
+            return Err(UnitDefinitionError::BuiltIn {
+                name: (*name).into(),
+            });
+        }
+
+        let name = names.first().copied().unwrap_or_default();
+
+        let def = Arc::new(CustomDef {
+            name: name.into(),
+            powers: powers.into(),
+            conversion: Some(conversion),
+        });
+
+        let unit = Unit::Custom(Custom {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            def,
+        });
+
+        for name in names {
+            self.names.insert((*name).into(), unit.clone());
+        }
+
+        Ok(unit)
+    }
+}
+
+/// Test if the given name is a built-in unit, possibly with a prefix like
+/// `km`.
+fn is_built_in(name: &str) -> bool {
+    matches!(crate::generated::unit::parse(name), Some(("", ..)))
+}
+
+/// Convert a rational into a conversion fraction.
+fn fraction(value: &Rational) -> Result<ConversionFraction, UnitDefinitionError> {
+    if value.is_zero() || value.numer().is_negative() {
+        return Err(UnitDefinitionError::NonPositive);
+    }
+
+    let (Some(numer), Some(denom)) = (value.numer().to_u128(), value.denom().to_u128()) else {
+        return Err(UnitDefinitionError::TooLarge);
+    };
+
+    Ok(ConversionFraction { numer, denom })
+}
@@ -0,0 +1,57 @@
+//! Angle units based on the radian (`rad`).
+//!
+//! All angle units except for the radian itself are defined as rational
+//! multiples of `π` radians, see [Conversion::Pi].
+
+use crate::powers::Powers;
+use crate::unit::{Conversion, ConversionFraction, Derived, DerivedVtable, Unit};
+
+fn angle_powers(powers: &mut Powers, power: i32) {
+    powers.insert(Unit::Radian, power);
+}
+
+macro_rules! angle {
+    ($(#[$meta:meta])* pub static $name:ident = ($id:expr, $num:literal / $den:literal), $f:expr) => {
+        $(#[$meta])*
+        pub static $name: Derived = Derived {
+            id: $id,
+            vtable: &DerivedVtable {
+                powers: angle_powers,
+                format: $f,
+                conversion: Some(Conversion::Pi(ConversionFraction {
+                    numer: $num,
+                    denom: $den,
+                })),
+            },
+        };
+    };
+}
+
+angle! {
+    /// A degree `°` (`π/180 rad`) in [Unit::Radian].
+    pub static DEGREE = (crate::generated::ids::DEGREE, 1 / 180), |f, _| f.write_str("°")
+}
+
+angle! {
+    /// An arcminute `′` (`1/60°`) in [Unit::Radian].
+    pub static ARCMINUTE = (crate::generated::ids::ARCMINUTE, 1 / 10800), |f, _| f.write_str("′")
+}
+
+angle! {
+    /// An arcsecond `″` (`1/60′`) in [Unit::Radian].
+    pub static ARCSECOND = (crate::generated::ids::ARCSECOND, 1 / 648000), |f, _| f.write_str("″")
+}
+
+angle! {
+    /// A gradian `gon` (`π/200 rad`) in [Unit::Radian].
+    pub static GRADIAN = (crate::generated::ids::GRADIAN, 1 / 200), |f, _| f.write_str("gon")
+}
+
+angle! {
+    /// A turn (`2π rad`) in [Unit::Radian].
+    pub static TURN = (crate::generated::ids::TURN, 2 / 1), |f, pluralize| if pluralize {
+        f.write_str("turns")
+    } else {
+        f.write_str("turn")
+    }
+}
@@ -39,6 +39,21 @@ pub static BTU: Derived = Derived {
     },
 };
 
+/// A watt-hour or `3600J` with the `Wh` suffix.
+///
+/// See [JOULE].
+pub static WATT_HOUR: Derived = Derived {
+    id: crate::generated::ids::WATT_HOUR,
+    vtable: &DerivedVtable {
+        powers: JOULE.vtable.powers,
+        format: |f, _| write!(f, "Wh"),
+        conversion: Some(Conversion::Factor(ConversionFraction {
+            numer: 3600,
+            denom: 1,
+        })),
+    },
+};
+
 /// Electronvolt `eV`.
 pub static ELECTRONVOLT: Derived = Derived {
     id: crate::generated::ids::ELECTRONVOLT,
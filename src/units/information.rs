@@ -0,0 +1,18 @@
+//! Special information units.
+
+use crate::unit::{Conversion, ConversionFraction, Derived, DerivedVtable, Unit};
+
+/// A bit `bit` (`1/8 B`) in [Unit::Byte].
+pub static BIT: Derived = Derived {
+    id: crate::generated::ids::BIT,
+    vtable: &DerivedVtable {
+        powers: |powers, p| {
+            powers.insert(Unit::Byte, p);
+        },
+        format: |f, _| write!(f, "bit"),
+        conversion: Some(Conversion::Factor(ConversionFraction {
+            numer: 1,
+            denom: 8,
+        })),
+    },
+};
@@ -32,8 +32,8 @@ pub static DALTON: Derived = Derived {
         },
         format: |f, _| write!(f, "Da"),
         conversion: Some(Conversion::Factor(ConversionFraction {
-            numer: 332107813321,
-            denom: 200000000000,
+            numer: 166053906660,
+            denom: 100000000000000000000000000000000000000,
         })),
     },
 };
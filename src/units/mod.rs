@@ -4,8 +4,10 @@
 
 use crate::unit::{Conversion, ConversionFraction, Derived, DerivedVtable, Unit};
 
+pub mod angle;
 pub mod area;
 pub mod energy;
+pub mod information;
 pub mod length;
 pub mod mass;
 pub mod temperature;
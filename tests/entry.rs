@@ -3,20 +3,73 @@ use anything::{Compound, Unit};
 use num::ToPrimitive;
 use std::iter::FromIterator;
 
+/// Evaluate the given query and return all of its values, which must all
+/// succeed.
+///
+/// The facts, context, options and descriptions to evaluate the query with can
+/// be given after it in that order, like `query_all!(source, ctx = &mut ctx)`.
+/// By default a fresh context is used with the default options.
 #[macro_export]
-macro_rules! query {
-    ($expr:expr) => {{
-        let db = anything::Db::in_memory().unwrap();
-        let options = Default::default();
-        let mut descriptions = Vec::new();
+macro_rules! query_all {
+    (@or [] $default:expr) => {
+        $default
+    };
+
+    (@or [$value:expr] $default:expr) => {
+        $value
+    };
+
+    (@results $expr:expr
+        $(, facts = $facts:expr)?
+        $(, ctx = $ctx:expr)?
+        $(, options = $options:expr)?
+        $(, descriptions = $descriptions:expr)?
+        $(,)?
+    ) => {{
+        let facts: &anything::Facts = query_all!(@or [$($facts)?] &anything::Facts::new());
+        let ctx: &mut anything::Context = query_all!(@or [$($ctx)?] &mut anything::Context::new());
+        let options: anything::Options = query_all!(@or [$($options)?] Default::default());
+        let descriptions: &mut Vec<anything::Description> =
+            query_all!(@or [$($descriptions)?] &mut Vec::new());
+        let db = anything::Db::in_memory_with(facts).unwrap();
         let parsed = anything::parse($expr).unwrap();
-        let mut values = anything::query(&parsed, &db, options, &mut descriptions);
-        let value = values.next().unwrap().unwrap();
+        anything::query(&parsed, &db, ctx, options, descriptions).collect::<Vec<_>>()
+    }};
+
+    ($($tt:tt)*) => {
+        query_all!(@results $($tt)*)
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    };
+}
+
+/// Evaluate the given query and return its single value.
+///
+/// Takes the same arguments as [query_all].
+#[macro_export]
+macro_rules! query {
+    ($($tt:tt)*) => {{
+        let mut values = query_all!($($tt)*).into_iter();
+        let value = values.next().unwrap();
         assert!(values.next().is_none());
         value
     }};
 }
 
+/// Evaluate the given query and return the first error it fails with.
+///
+/// Takes the same arguments as [query_all].
+#[macro_export]
+macro_rules! query_err {
+    ($($tt:tt)*) => {
+        query_all!(@results $($tt)*)
+            .into_iter()
+            .find_map(Result::err)
+            .unwrap()
+    };
+}
+
 #[macro_export]
 macro_rules! unit {
     ($expr:expr) => {
@@ -60,16 +113,42 @@ macro_rules! assert_query {
     };
 }
 
+#[path = "entry/angle.rs"]
+mod angle;
 #[path = "entry/areas.rs"]
 mod areas;
+#[path = "entry/auto.rs"]
+mod auto;
+#[path = "entry/check.rs"]
+mod check;
+#[path = "entry/chemistry.rs"]
+mod chemistry;
+#[path = "entry/currency.rs"]
+mod currency;
+#[path = "entry/date.rs"]
+mod date;
 #[path = "entry/energy.rs"]
 mod energy;
+#[path = "entry/facts.rs"]
+mod facts;
+#[path = "entry/functions.rs"]
+mod functions;
+#[path = "entry/import.rs"]
+mod import;
+#[path = "entry/information.rs"]
+mod information;
 #[path = "entry/length.rs"]
 mod length;
+#[path = "entry/lookup.rs"]
+mod lookup;
 #[path = "entry/mass.rs"]
 mod mass;
+#[path = "entry/mixed.rs"]
+mod mixed;
 #[path = "entry/temperature.rs"]
 mod temperature;
+#[path = "entry/uncertainty.rs"]
+mod uncertainty;
 #[path = "entry/velocity.rs"]
 mod velocity;
 #[path = "entry/volume.rs"]
@@ -145,6 +224,130 @@ fn test_times() {
     assert_query!("4decades + 6decades to centuries", 1, centuries);
 }
 
+#[test]
+fn test_variables() {
+    let mut ctx = anything::Context::new();
+
+    let values = query_all!("mass = 3kg; let v = 2m/s; mass * v", ctx = &mut ctx);
+    assert_eq!(values, [lit!(3, kg), lit!(2, m / s), lit!(6, kg * m / s)]);
+    assert_eq!(query!("mass to g", ctx = &mut ctx), lit!(3000, g));
+}
+
+#[test]
+fn test_last_result() {
+    let values = query_all!("3kg; _ * 2; ans to g");
+    assert_eq!(values, [lit!(3, kg), lit!(6, kg), lit!(6000, g)]);
+}
+
+#[test]
+fn test_functions() {
+    let mut ctx = anything::Context::new();
+
+    let source =
+        "kinetic(m: kg, v: m/s) = 0.5 * m * v * v; kinetic(2000g, 3m/s) to J; zero() = 0; zero()";
+    let values = query_all!(source, ctx = &mut ctx);
+
+    assert_eq!(values, [lit!(9, J), lit!(0)]);
+    assert!(ctx.has_function("kinetic"));
+
+    let source = "kinetic(2kg, 3s)";
+    let error = query_err!(source, ctx = &mut ctx);
+    assert_eq!(&source[error.range()], "3s");
+
+    let source = "add(a, b) = a + b; add(1kg, 1m)";
+    let error = query_err!(source, ctx = &mut ctx);
+    assert_eq!(&source[error.range()], "add(1kg, 1m)");
+}
+
+#[test]
+fn test_custom_units() {
+    let mut ctx = anything::Context::new();
+
+    ctx.units_mut()
+        .load_toml(
+            r#"
+            [[units]]
+            names = ["degR"]
+            offset = "273.15"
+            unit = "K"
+            "#,
+        )
+        .unwrap();
+
+    let source = "unit smoot = 1.7018 m; 1km to smoot; 2 smoot to m; 300K to degR";
+    let values = query_all!(source, ctx = &mut ctx);
+
+    assert_eq!(values.len(), 3);
+    assert_eq!(values[0].value, ratio!(10000000 / 17018));
+    assert_eq!(values[0].unit.to_string(), "smoot");
+    assert_eq!(values[1], lit!(34036 / 10000, m));
+    assert_eq!(values[2].value, ratio!(2685 / 100));
+    assert_eq!(values[2].unit.to_string(), "degR");
+
+    for source in ["unit nothing = 0 m", "unit m = 5 s", "unit km = 3 m"] {
+        query_err!(source, ctx = &mut ctx);
+    }
+
+    assert!(matches!(
+        ctx.units_mut().define(&["s"], lit!(2, kg)),
+        Err(anything::UnitDefinitionError::BuiltIn { .. })
+    ));
+}
+
+#[test]
+fn test_powers() {
+    assert_query!("(3m)^2", 9, m ^ 2);
+    assert_query!("(2m)^-2", 1 / 4, m ^ -2);
+    assert_query!("4^(3/2)", 8);
+    assert_query!("sqrt(9 m^2)", 3, m);
+    assert_query!("cbrt(27 m^3 / 8 s^6)", 3 / 2, m / s ^ 2);
+    assert_eq!(query!("(-8)^(1/3)").value, anything::Rational::new(-2, 1));
+    assert_query!("sqrt(1 hectare)", 100, m);
+    assert_query!("(2^100)^(1/100)", 2);
+
+    let n = query!("1^(1/100)");
+    assert!(n.exact);
+    assert_eq!(n, lit!(1));
+
+    let n = query!("2^100000 / 2^99999");
+    assert_eq!(n, lit!(2));
+    assert_eq!(query!("(-1)^2000000001"), query!("-1"));
+
+    let n = query!("sqrt(2)");
+    assert!(n.unit.is_empty());
+    assert!(!n.exact);
+    assert_eq!(
+        n.value.display(&Default::default()).to_string()[..7],
+        *"1.41421"
+    );
+}
+
+#[test]
+fn test_power_too_large() {
+    for source in [
+        "(1 m^2)^2000000000",
+        "(1m)^2147483647 * 1m",
+        "1m / (1m)^-32767",
+        "2^2000000000",
+        "(1/3)^-2000000000",
+        "(10^1000)^(3000/7)",
+    ] {
+        let error = query_err!(source);
+        assert_eq!(error.to_string(), "the power is too large", "{source}");
+    }
+}
+
+#[test]
+fn test_exactness() {
+    assert!(query!("sqrt(4 m^2) + 2 m").exact);
+    assert!(query!("round(sin(1) * 100)").value.is_integer());
+    assert!(!query!("round(sin(1) * 100)").exact);
+    assert!(!query!("sqrt(2) * 0").exact);
+    assert!(!query!("2^0.123456789").exact);
+    assert!(query!("g0 to ft/s^2").exact);
+    assert!(!query!("pi").exact);
+}
+
 #[test]
 fn test_relaxed_parser() {
     assert_query!("1 newton second", 1, Ns);
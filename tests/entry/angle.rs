@@ -0,0 +1,31 @@
+use num::ToPrimitive;
+
+#[test]
+fn test_angles() {
+    assert_query!("90° to turn", 1 / 4, turn);
+    assert_query!("1 turn to arcsec", 1296000, arcsec);
+    assert_query!("400 gon to turns", 1, turn);
+    assert_query!("90 degrees to arcmin", 5400, arcmin);
+    assert_query!("5′ to ″", 300, arcsec);
+
+    let n = query!("30 deg to rad");
+    assert!(!n.exact);
+    assert_eq!(n.unit, unit!("rad"));
+    assert!((n.value.to_f64().unwrap() - std::f64::consts::FRAC_PI_6).abs() < 1e-12);
+}
+
+#[test]
+fn test_trigonometry_with_angles() {
+    assert_query!("sin(90°)", 1);
+    assert_query!("cos(180 deg) + 2", 1);
+    assert_query!("sin(100 gon) + cos(1 turn)", 2);
+    assert_query!("tan(180 deg)", 0);
+
+    let n = query!("sin(30 deg)");
+    assert!(!n.exact);
+    assert!((n.value.to_f64().unwrap() - 0.5).abs() < 1e-12);
+
+    let n = query!("asin(1) to deg");
+    assert!(!n.exact);
+    assert_eq!(n.unit, unit!("deg"));
+}
@@ -0,0 +1,47 @@
+use anything::Options;
+
+#[test]
+fn test_auto_named_units() {
+    let auto = Options::default().auto_units();
+
+    assert_eq!(query!("3 kN * 2 m", options = auto), lit!(6, kJ));
+    assert_eq!(query!("12 V * 3 A", options = auto), lit!(36, W));
+    assert_eq!(query!("100 kg*m/s^2", options = auto), lit!(100, N));
+    assert_eq!(query!("3N / 10kg", options = auto), lit!(3 / 10, m / s ^ 2));
+    assert_eq!(
+        query!("1000Gbtu to auto", options = auto),
+        lit!(211 / 200, PJ)
+    );
+}
+
+#[test]
+fn test_auto_prefixes() {
+    let auto = Options::default().auto_units();
+
+    assert_eq!(query!("1500 m", options = auto), lit!(3 / 2, km));
+    assert_eq!(query!("0.002 kg", options = auto), lit!(2, g));
+
+    let n = query!("0.0005 s", options = auto);
+    assert_eq!(n.value, ratio!(500));
+    assert_eq!(n.unit.to_string(), "μs");
+
+    // Readable values and units which aren't commonly prefixed are kept.
+    assert_eq!(query!("5 cm", options = auto), lit!(5, cm));
+    assert_eq!(query!("5000 kg", options = auto), lit!(5000, kg));
+    assert_eq!(query!("3600 s", options = auto), lit!(3600, s));
+    assert_eq!(query!("1000 ft", options = auto), lit!(1000, ft));
+}
+
+#[test]
+fn test_auto_cast() {
+    let auto = Options::default().auto_units();
+
+    assert_eq!(query!("1500 m to m", options = auto), lit!(1500, m));
+    assert_eq!(query!("1500 m"), lit!(1500, m));
+    assert_eq!(query!("1500 m to auto"), lit!(3 / 2, km));
+
+    // NB: explicit casts keep the unit as it was written.
+    let n = query!("1000Gbtu to MWh", options = auto);
+    assert_eq!(n.value, ratio!(2637500 / 9));
+    assert_eq!(n.unit.to_string(), "MWh");
+}
@@ -0,0 +1,16 @@
+use anything::Severity;
+
+/// The embedded database must not have any errors, run
+/// `cargo run -p assets -- check` to see every problem with it.
+#[test]
+#[ignore = "db/astronomics.bin.gz and db/populations.bin.gz have to be regenerated with `cargo run -p assets`"]
+fn test_embedded_database() {
+    let errors = anything::check()
+        .unwrap()
+        .into_iter()
+        .filter(|p| p.severity == Severity::Error)
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>();
+
+    assert!(errors.is_empty(), "errors:\n{}", errors.join("\n"));
+}
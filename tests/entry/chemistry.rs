@@ -0,0 +1,166 @@
+use anything::Facts;
+
+// NB: the element facts downloaded by `cargo run -p assets` aren't bundled, so
+// the elements these tests need are given here.
+const ELEMENTS: &str = r#"
+[[constants]]
+entity = "Hydrogen"
+attribute = "atomic mass"
+description = "Atomic mass of Hydrogen"
+value = "1.008"
+unit = "Da"
+
+[[constants]]
+entity = "Carbon"
+attribute = "atomic mass"
+description = "Atomic mass of Carbon"
+value = "12.011"
+unit = "Da"
+
+[[constants]]
+entity = "Oxygen"
+attribute = "atomic mass"
+description = "Atomic mass of Oxygen"
+value = "15.999"
+unit = "Da"
+
+[[constants]]
+entity = "Sodium"
+attribute = "atomic mass"
+description = "Atomic mass of Sodium"
+value = "22.99"
+unit = "Da"
+
+[[constants]]
+entity = "Sulfur"
+attribute = "atomic mass"
+description = "Atomic mass of Sulfur"
+value = "32.06"
+unit = "Da"
+
+[[constants]]
+entity = "Chlorine"
+attribute = "atomic mass"
+description = "Atomic mass of Chlorine"
+value = "35.45"
+unit = "Da"
+
+[[constants]]
+entity = "Technetium"
+attribute = "atomic mass"
+description = "Atomic mass of Technetium"
+value = "97"
+unit = "Da"
+
+[[constants]]
+entity = "Gold"
+attribute = "density"
+description = "Density of Gold"
+value = "19.3"
+unit = "g/cm^3"
+
+[[constants]]
+entity = "Iron"
+attribute = "melting point"
+description = "Melting point of Iron"
+value = "1811"
+unit = "K"
+"#;
+
+/// The elements the tests need.
+fn elements() -> Facts {
+    let mut facts = Facts::new();
+    facts.load_toml(ELEMENTS).unwrap();
+    facts
+}
+
+#[test]
+fn test_element_facts() {
+    let facts = elements();
+
+    let n = query!("hydrogen.atomic_mass", facts = &facts);
+    assert!(!n.exact);
+    assert_eq!(n.value, ratio!(1008 / 1000));
+    assert_eq!(n.unit, unit!("Da"));
+
+    let n = query!("density of gold", facts = &facts);
+    assert_eq!(n.value, ratio!(193 / 10));
+    assert_eq!(n.unit, unit!("g/cm^3"));
+
+    let n = query!("melting point of iron", facts = &facts);
+    assert_eq!(n.value, ratio!(1811));
+    assert_eq!(n.unit, unit!("K"));
+}
+
+#[test]
+fn test_molar_mass() {
+    let facts = elements();
+
+    let n = query!("molar_mass(H2SO4)", facts = &facts);
+    assert!(!n.exact);
+    assert_eq!(n.value, ratio!(98072 / 1000));
+    assert_eq!(n.unit, unit!("g/mol"));
+
+    let value = |source| query!(source, facts = &facts).value;
+
+    assert_eq!(value("molar_mass(NaCl)"), ratio!(5844 / 100));
+    assert_eq!(value("molar_mass(C6H12O6)"), ratio!(180156 / 1000));
+    assert_eq!(value("molar_mass(Tc)"), ratio!(97));
+
+    assert_eq!(
+        query_err!("molar_mass(H2Xy)", facts = &facts).to_string(),
+        "`H2Xy` is not a chemical formula"
+    );
+    assert_eq!(
+        query_err!("molar_mass(H2O, H2)", facts = &facts).to_string(),
+        "bad number of arguments, got 2 but expected 1"
+    );
+}
+
+#[test]
+fn test_stoichiometry() {
+    let facts = elements();
+
+    let n = query!("18.015 g H2O to mol", facts = &facts);
+    assert!(!n.exact);
+    assert_eq!(n.value, ratio!(1));
+    assert_eq!(n.unit, unit!("mol"));
+
+    let n = query!("2 mol NaCl", facts = &facts);
+    assert_eq!(n.value, ratio!(11688 / 100));
+    assert_eq!(n.unit, unit!("g"));
+
+    let n = query!("0.5 kmol H2SO4 to kg", facts = &facts);
+    assert_eq!(n.value, ratio!(49036 / 1000));
+    assert_eq!(n.unit, unit!("kg"));
+
+    let n = query!("10 g H2O to g", facts = &facts);
+    assert!(n.exact);
+    assert_eq!(n.value, ratio!(10));
+    assert_eq!(n.unit, unit!("g"));
+
+    let n = query!("2 mol NaCl to mol", facts = &facts);
+    assert_eq!(n.value, ratio!(2));
+    assert_eq!(n.unit, unit!("mol"));
+
+    // NB: a formula only names a substance after a mass or an amount, and
+    // never when it is a single unit.
+    assert_eq!(query!("2 J/mol K", facts = &facts).unit, unit!("J/mol*K"));
+    assert_eq!(query!("1 mol K", facts = &facts).unit, unit!("mol*K"));
+    assert_eq!(query!("1 mol C", facts = &facts).unit, unit!("mol*C"));
+
+    let n = query!("3 mol K * 2", facts = &facts);
+    assert!(n.exact);
+    assert_eq!(n.value, ratio!(6));
+    assert_eq!(n.unit, unit!("mol*K"));
+}
+
+#[test]
+#[ignore = "db/elements.bin.gz has to be generated with `cargo run -p assets`"]
+fn test_embedded_elements() {
+    assert_eq!(
+        query!("molar_mass(H2O)").value,
+        query!("2 * hydrogen.atomic_mass + oxygen.atomic_mass").value
+    );
+    assert_eq!(query!("gold.density").unit, unit!("g/cm^3"));
+}
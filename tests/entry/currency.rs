@@ -0,0 +1,94 @@
+use anything::{Context, Db, Description, Options, Rates};
+
+const RATES: &str = r#"
+[[days]]
+date = "2024-12-31"
+rates = { USD = "1.25", SEK = "11.5" }
+
+[[days]]
+date = "2025-01-31"
+rates = { USD = "1.0" }
+"#;
+
+/// A context with the exchange rates which apply on the given date.
+fn context(as_of: Option<&str>) -> Context {
+    let mut ctx = Context::new();
+    let mut rates = Rates::new();
+    rates.load_toml(RATES).unwrap();
+    ctx.define_currencies(rates.as_of(as_of).unwrap()).unwrap();
+    ctx
+}
+
+#[test]
+fn test_currency() {
+    let n = query!(
+        "200 USD/hr * 40 hr to EUR",
+        ctx = &mut context(Some("2025-01-15"))
+    );
+    assert_eq!(n, lit!(6400, EUR));
+
+    let n = query!("200 USD/hr * 40 hr to EUR", ctx = &mut context(None));
+    assert_eq!(n, lit!(8000, EUR));
+
+    let n = query!("10 USD to SEK", ctx = &mut context(Some("2024-12-31")));
+    assert_eq!(n.value, ratio!(92));
+    assert_eq!(n.unit.to_string(), "SEK");
+
+    let n = query!("5 € to euros", ctx = &mut context(None));
+    assert_eq!(n, lit!(5, EUR));
+}
+
+#[test]
+fn test_currency_describe() {
+    let mut descriptions = Vec::new();
+
+    query!(
+        "3 USD + 4 USD to EUR",
+        ctx = &mut context(None),
+        options = Options::default().describe(),
+        descriptions = &mut descriptions,
+    );
+
+    let [Description::ExchangeRate(rate)] = &descriptions[..] else {
+        panic!("expected a single exchange rate");
+    };
+
+    assert_eq!(&*rate.code, "USD");
+    assert_eq!(&*rate.date, "2025-01-31");
+    assert_eq!(rate.rate, ratio!(1));
+    assert_eq!(rate.source, None);
+}
+
+#[test]
+#[ignore = "db/currencies.bin.gz has to be generated with `cargo run -p assets`"]
+fn test_embedded_rates() {
+    let rates = Rates::embedded().unwrap();
+    let day = rates.as_of(None).unwrap();
+    assert!(day.rates.contains_key("USD"));
+    assert!(day
+        .source
+        .is_some_and(|id| Db::in_memory().unwrap().get_source(id).is_some()));
+}
+
+#[test]
+fn test_rates_dates() {
+    let mut rates = Rates::new();
+    rates.load_toml(RATES).unwrap();
+
+    assert!(rates.as_of(Some("2024-02-31")).is_err());
+    assert!(rates.as_of(Some("2025-02-29")).is_err());
+    assert!(rates.as_of(Some("2025-01-15T12:00")).is_err());
+    assert_eq!(
+        &*rates.as_of(Some("2028-02-29")).unwrap().date,
+        "2025-01-31"
+    );
+
+    let mut rates = Rates::new();
+    let error = rates
+        .load_toml("[[days]]\ndate = \"2024-02-31\"\nrates = { USD = \"1.0\" }")
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "bad date `2024-02-31`, expected `YYYY-MM-DD`"
+    );
+}
@@ -0,0 +1,92 @@
+use anything::Kind;
+
+/// Evaluate the given query and return it formatted as an instant.
+fn instant(source: &str) -> String {
+    query!(source).display_instant().unwrap()
+}
+
+#[test]
+fn test_dates() {
+    assert_eq!(instant("2026-10-18"), "2026-10-18");
+    assert_eq!(
+        instant("2024-02-29T23:59:59.25Z"),
+        "2024-02-29T23:59:59.25Z"
+    );
+    assert_eq!(
+        instant("2026-10-18T09:30+02:00"),
+        "2026-10-18T09:30:00+02:00"
+    );
+    assert_eq!(
+        instant("2026-10-18T09:30+02:00 to utc"),
+        "2026-10-18T07:30:00Z"
+    );
+    assert_eq!(instant("1969-12-31T23:59:59Z"), "1969-12-31T23:59:59Z");
+
+    let n = query!("2026-10-18T09:30-05:00");
+    assert_eq!(n.kind, Kind::Instant { offset: -300 });
+    assert_eq!(n.value, ratio!(1792333800));
+}
+
+#[test]
+fn test_date_arithmetic() {
+    assert_eq!(instant("2026-10-18 + 90 dy"), "2027-01-16");
+    assert_eq!(instant("2 wk + 2026-10-18"), "2026-11-01");
+    assert_eq!(instant("2026-03-01 - 1 dy"), "2026-02-28");
+    assert_eq!(instant("2026-10-18T22:00Z + 3 hr"), "2026-10-19T01:00:00Z");
+    assert_eq!(
+        instant("2026-10-18T09:30+02:00 + 1 dy"),
+        "2026-10-19T09:30:00+02:00"
+    );
+
+    assert_query!("2027-01-01 - 2026-10-18 to dy", 75, dy);
+    assert_query!("2026-10-25 - 2026-10-18 to wk", 1, wk);
+    assert_query!("2026-10-18 to unix", 1792281600);
+
+    let n = query!("2026-10-18 - 2026-10-18T12:00Z");
+    assert_eq!(n.kind, Kind::Quantity);
+    assert_eq!(n.value, anything::Rational::new(-43200, 1));
+    assert_eq!(n.unit, unit!("s"));
+}
+
+#[test]
+fn test_now() {
+    assert!(query!("now").is_instant());
+    assert!(query!("today").is_instant());
+    assert!(!query!("now to unix").is_instant());
+    assert!(!query!("2027-01-01 - today to wk").is_instant());
+}
+
+#[test]
+fn test_date_errors() {
+    assert_eq!(
+        query_err!("2026-10-18 + 2026-10-18").to_string(),
+        "illegal operation: instant + instant"
+    );
+    assert_eq!(
+        query_err!("2026-10-18 * 2").to_string(),
+        "illegal operation: instant * a number"
+    );
+    assert_eq!(
+        query_err!("2026-10-18 + 5").to_string(),
+        "illegal operation: instant + a number"
+    );
+    assert_eq!(
+        query_err!("1e30 years + 2026-10-18").to_string(),
+        "instant in time is too far from 1970-01-01, it must be within a billion years"
+    );
+    assert_eq!(
+        query_err!("2026-10-18 - 1e30 years").to_string(),
+        "instant in time is too far from 1970-01-01, it must be within a billion years"
+    );
+    assert_eq!(
+        query_err!("2026-10-18 to s").to_string(),
+        "cannot cast an instant in time to `s`, only to `unix` or `utc`"
+    );
+    assert_eq!(
+        query_err!("5 s to unix").to_string(),
+        "expected an instant in time, but got a value with unit `s`"
+    );
+    assert!(query_err!("2026-02-29")
+        .to_string()
+        .starts_with("bad date `2026-02-29`"));
+}
@@ -0,0 +1,117 @@
+use anything::Facts;
+
+const FACTS: &str = r#"
+[[constants]]
+tokens = ["fleet", "size"]
+description = "Number of trucks in the fleet"
+value = "42"
+exact = true
+
+[[constants]]
+tokens = ["truck", "range"]
+description = "Range of a truck on a full tank"
+value = "800"
+unit = "km"
+uncertainty = "50"
+
+[[constants]]
+tokens = ["population", "testland"]
+description = "Population of Testland"
+series = { "1990" = "1000", "2000" = "1500", "2010" = "2250" }
+
+[[constants]]
+entity = "Depot"
+attribute = "floor area"
+description = "Floor area of the depot"
+value = "1200"
+unit = "m^2"
+"#;
+
+#[test]
+fn test_user_facts() {
+    let mut facts = Facts::new();
+    facts.load_toml(FACTS).unwrap();
+    assert_eq!(facts.constants().len(), 4);
+
+    assert_eq!(query!("fleet size * 2", facts = &facts), lit!(84));
+
+    let n = query!("fleet size * truck range", facts = &facts);
+    assert!(!n.exact);
+    assert_eq!(n.value, ratio!(33600));
+    assert_eq!(n.unit, unit!("km"));
+    assert_eq!(n.uncertainty, Some(ratio!(2100)));
+}
+
+#[test]
+fn test_bad_user_facts() {
+    let mut facts = Facts::new();
+
+    let error = facts
+        .load_toml("[[constants]]\ntokens = [\"x\"]\ndescription = \"X\"\nvalue = \"abc\"")
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "X: bad value `abc`");
+
+    let error = facts
+        .load_toml(
+            "[[constants]]\ntokens = [\"x\"]\ndescription = \"X\"\nvalue = \"1\"\nunit = \"blarg\"",
+        )
+        .unwrap_err();
+
+    assert!(error.to_string().starts_with("X: bad unit `blarg`"));
+    assert!(facts.constants().is_empty());
+}
+
+#[test]
+fn test_series() {
+    let mut facts = Facts::new();
+    facts.load_toml(FACTS).unwrap();
+
+    let value = |source| query!(source, facts = &facts).value;
+
+    assert_eq!(value("population testland"), ratio!(2250));
+    assert_eq!(value("population testland in 1990"), ratio!(1000));
+    assert_eq!(value("population testland in 2005"), ratio!(1500));
+    assert_eq!(value("population(testland)"), ratio!(2250));
+    assert_eq!(value("population(testland, 2000)"), ratio!(1500));
+    assert_eq!(
+        value("population testland in 2010 / population testland in 2000"),
+        ratio!(3 / 2)
+    );
+
+    assert_eq!(
+        query_err!("population testland in 1980", facts = &facts).to_string(),
+        "no value for `population testland` in or before 1980"
+    );
+    assert_eq!(
+        query_err!("fleet size in 2000", facts = &facts).to_string(),
+        "`fleet size` doesn't change over time, so it has no value in a given year"
+    );
+    assert_eq!(
+        query_err!("populaton(testland, 2000)", facts = &facts).to_string(),
+        "missing function `populaton`"
+    );
+}
+
+#[test]
+fn test_entity_facts() {
+    let mut facts = Facts::new();
+    facts.load_toml(FACTS).unwrap();
+
+    let depot = &facts.constants()[3];
+    assert_eq!(
+        depot.tokens,
+        ["floor".into(), "area".into(), "depot".into()]
+    );
+
+    let area = query!("depot.floor_area", facts = &facts);
+    assert_eq!(area.value, ratio!(1200));
+    assert_eq!(area.unit, unit!("m^2"));
+    assert_eq!(query!("floor area of the depot", facts = &facts), area);
+
+    let error = facts
+        .load_toml("[[constants]]\nentity = \"x\"\ndescription = \"X\"\nvalue = \"1\"")
+        .unwrap_err();
+
+    assert_eq!(error.to_string(), "X: missing tokens");
+}
@@ -0,0 +1,62 @@
+#[test]
+fn test_exact_functions() {
+    assert_query!("exp(0)", 1);
+    assert_query!("ln(1)", 0);
+    assert_query!("log10(1000)", 3);
+    assert_query!("log(81, 3)", 4);
+    assert_query!("log2(1/8) + 3", 0);
+    assert_query!("log(8, 1/2) + 3", 0);
+    assert_query!("log10(10^400)", 400);
+    assert_query!("abs(2m - 5m)", 3, m);
+    assert_query!("min(3m, 200cm, 4m)", 2, m);
+    assert_query!("max(3m, 200cm, 4m)", 4, m);
+    assert_query!("hypot(3m, 400cm)", 5, m);
+    assert_query!("gcd(12, 18, 27)", 3);
+    assert_query!("lcm(4, 6)", 12);
+    assert_query!("factorial(20)", 2432902008176640000);
+    assert_query!("round(1.2345, 2)", 123 / 100);
+}
+
+#[test]
+fn test_approximate_functions() {
+    for query in [
+        "tan(1)", "asin(1)", "acos(0)", "atan(1)", "sinh(1)", "cosh(1)", "tanh(1)",
+    ] {
+        assert!(!query!(query).exact, "{query}");
+    }
+
+    // NB: the exponent isn't calculated when it can't be an exact power.
+    let n = query!("log(10^300, 1.000001)");
+    assert!(!n.exact);
+    assert!(n.value > ratio!(690775873) && n.value < ratio!(690775874));
+
+    let n = query!("atan2(1m, 100cm) * 4");
+    assert!(!n.exact);
+    assert_eq!(n.unit, unit!("rad"));
+    assert_eq!(
+        n.value.display(&Default::default()).to_string()[..6],
+        *"3.1415"
+    );
+}
+
+#[test]
+fn test_function_errors() {
+    for (source, at) in [
+        ("sin(3 m)", "3 m"),
+        ("tan(90 deg)", "90 deg"),
+        ("exp(2 s)", "2 s"),
+        ("sin(1, 2)", "2"),
+        ("asin(2)", "2"),
+        ("ln(0)", "0"),
+        ("log(10, 1)", "1"),
+        ("atan2(1m, 1s)", "1s"),
+        ("max(1m, 2s)", "2s"),
+        ("gcd(3, 1.5)", "1.5"),
+        ("factorial(5 m)", "5 m"),
+        ("factorial(0 - 1)", "0 - 1"),
+        ("round(1.5, 0.5)", "0.5"),
+    ] {
+        let error = query_err!(source);
+        assert_eq!(&source[error.range()], at, "{source}");
+    }
+}
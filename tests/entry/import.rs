@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anything::{Db, Facts, Import};
+
+/// Write the given files into a fresh temporary directory.
+fn files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("anything-{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    for (path, content) in files {
+        fs::write(dir.join(path), content).unwrap();
+    }
+
+    dir
+}
+
+const FLEET_CSV: &str = "\
+Truck,Range (km),Range error (km),Payload
+Truck 7,800,50,12.5
+\"Truck \"\"Big\"\" 9\",1200,,30
+,5,,
+Truck 11,,,18
+";
+
+const FLEET: &str = r#"
+file = "fleet.csv"
+entity = "Truck"
+
+[source]
+description = "Fleet spreadsheet"
+
+[[attributes]]
+attribute = "range"
+column = "Range (km)"
+unit = "km"
+uncertainty = "Range error (km)"
+
+[[attributes]]
+attribute = "payload"
+column = "Payload"
+unit = "t"
+"#;
+
+#[test]
+fn test_import_csv() {
+    let dir = files(
+        "import-csv",
+        &[("fleet.csv", FLEET_CSV), ("fleet.toml", FLEET)],
+    );
+
+    let import = Import::open(&dir.join("fleet.toml")).unwrap();
+    assert_eq!(import.len(), 5);
+
+    let mut facts = Facts::new();
+    facts.load_import(&import).unwrap();
+
+    let db = Db::in_memory_with(&facts).unwrap();
+
+    let range = db.get("truck_7", "range").unwrap().unwrap();
+    assert_eq!(&*range.description, "Range of Truck 7");
+    assert_eq!(range.value, ratio!(800));
+    assert_eq!(range.unit, unit!("km"));
+    assert_eq!(range.uncertainty, Some(ratio!(50)));
+
+    let payload = db.get("truck \"big\" 9", "payload").unwrap().unwrap();
+    assert_eq!(payload.value, ratio!(30));
+    assert!(db.get("truck 11", "range").unwrap().is_none());
+
+    assert_eq!(facts.sources().len(), 1);
+    assert_eq!(&*facts.sources()[0].description, "Fleet spreadsheet");
+    assert_eq!(range.source, Some(facts.sources()[0].id));
+
+    // NB: the imported facts can be stored and loaded again.
+    let stored = dir.join("stored.toml");
+    fs::write(&stored, import.to_toml().unwrap()).unwrap();
+
+    let mut loaded = Facts::new();
+    loaded.load_path(&stored).unwrap();
+    assert_eq!(loaded.constants().len(), 5);
+    assert_eq!(&*loaded.sources()[0].description, "Fleet spreadsheet");
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_import_json() {
+    let json = r#"{"data": {"moons": [
+        {"name": "Deimos", "mass": 1.4762e15, "albedo": "0.068"},
+        {"name": "Phobos", "mass": 10.6e15, "albedo": null}
+    ]}}"#;
+
+    let mapping = r#"
+file = "moons.json"
+records = "/data/moons"
+entity = "name"
+
+[[attributes]]
+attribute = "mass"
+column = "mass"
+unit = "kg"
+description = "Mass of the moon {entity}"
+
+[[attributes]]
+attribute = "albedo"
+column = "albedo"
+"#;
+
+    let dir = files(
+        "import-json",
+        &[("moons.json", json), ("moons.toml", mapping)],
+    );
+
+    let mut facts = Facts::new();
+    facts
+        .load_import(&Import::open(&dir.join("moons.toml")).unwrap())
+        .unwrap();
+
+    let constants = facts.constants();
+    assert_eq!(constants.len(), 3);
+    assert_eq!(&*constants[0].description, "Mass of the moon Deimos");
+    assert_eq!(constants[0].value, ratio!(1476200000000000));
+    assert_eq!(constants[1].value, ratio!(68 / 1000));
+    assert_eq!(
+        &*facts.sources()[0].description,
+        "Facts imported from moons.json"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_import_errors() {
+    let missing = FLEET.replace("Payload", "Weight");
+    let bad = FLEET_CSV.replace("12.5", "\"12,5\"");
+
+    let dir = files(
+        "import-errors",
+        &[
+            ("fleet.csv", FLEET_CSV),
+            ("bad.csv", &bad),
+            ("missing.toml", &missing),
+            ("bad.toml", &FLEET.replace("fleet.csv", "bad.csv")),
+        ],
+    );
+
+    let error = Import::open(&dir.join("missing.toml")).err().unwrap();
+    assert_eq!(error.root_cause().to_string(), "missing column `Weight`");
+
+    let error = Import::open(&dir.join("bad.toml")).err().unwrap();
+    assert!(format!("{error:#}").contains("Payload of Truck 7: bad value `12,5`"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,19 @@
+#[test]
+fn test_binary_prefixes() {
+    assert_query!("1 GiB to MB", 1073741824 / 1000000, MB);
+    assert_query!("3 MiB to KiB", 3072, KiB);
+    assert_query!("1 kibibyte to B", 1024, B);
+    assert_query!("1 TiB to GiB", 1024, GiB);
+    assert_query!("1 YiB to ZiB", 1024, ZiB);
+
+    assert_eq!(unit!("KiB").to_string(), "KiB");
+    assert_eq!(unit!("Gibit/s").to_string(), "Gibit/s");
+}
+
+#[test]
+fn test_bits() {
+    assert_query!("8 bit to B", 1, B);
+    assert_query!("100 Mbit/s * 1 hr to GB", 45, GB);
+    assert_query!("1 Gib to MiB", 128, MiB);
+    assert_query!("1 kB to b", 8000, b);
+}
@@ -0,0 +1,173 @@
+use anything::{Candidate, Db, Description, Facts, Options};
+
+const ENTITIES: &str = r#"
+[[constants]]
+entity = "Mars"
+attribute = "mass"
+description = "Mass of Mars"
+value = "6.4171e23"
+unit = "kg"
+
+[[constants]]
+entity = "Mars"
+attribute = "radius"
+description = "Radius of Mars"
+value = "3389.5"
+unit = "km"
+
+[[constants]]
+entity = "United States of America"
+attribute = "population"
+description = "Population of United States of America"
+value = "331002647"
+
+[[constants]]
+tokens = ["mass", "earth"]
+description = "Mass of Earth"
+value = "5.9722e24"
+unit = "kg"
+"#;
+
+/// The description of a candidate, without the year that populations which
+/// aren't backed by a series of values end with.
+fn description(candidate: &Candidate) -> &str {
+    candidate.constant.description.trim_end_matches(" in 2020")
+}
+
+#[test]
+fn test_lookup_candidates() {
+    let db = Db::in_memory().unwrap();
+    let candidates = db.lookup("population niger").unwrap();
+
+    assert!(candidates.len() > 1);
+    assert!(candidates.windows(2).all(|w| w[0].score >= w[1].score));
+    assert_eq!(description(&candidates[0]), "Population of Niger");
+    assert_eq!(description(&candidates[1]), "Population of Nigeria");
+}
+
+#[test]
+fn test_list_source() {
+    let db = Db::in_memory().unwrap();
+
+    let source = db
+        .sources()
+        .find(|s| s.description.contains("NASA"))
+        .unwrap();
+
+    let constants = db.list(source.id).unwrap();
+
+    assert!(constants.iter().all(|c| c.source == Some(source.id)));
+    assert!(constants.iter().any(|c| &*c.description == "Mass of Earth"));
+    assert!(db.list(0).unwrap().is_empty());
+}
+
+#[test]
+fn test_lookup_ambiguous() {
+    let candidates = Options::default().candidates();
+
+    let error = query_err!("earth", options = candidates);
+    assert_eq!(
+        error.to_string(),
+        "`earth` is ambiguous, it could be: Mass of Earth; Radius of Earth; Diameter of Earth"
+    );
+
+    query!("mass of earth", options = candidates);
+    query!("G", options = candidates);
+
+    // NB: an exact match wins over a similarly scoring candidate.
+    let mut descriptions = Vec::new();
+    query!(
+        "population niger",
+        options = candidates,
+        descriptions = &mut descriptions
+    );
+
+    let [Description::Candidates(query, candidates)] = &descriptions[..] else {
+        panic!("expected a single set of candidates");
+    };
+
+    assert_eq!(&**query, "population niger");
+    assert_eq!(description(&candidates[0]), "Population of Niger");
+}
+
+#[test]
+fn test_search_special_characters() {
+    let db = Db::in_memory().unwrap();
+
+    for query in ["population (côte d'ivoire)", "-population: \\*"] {
+        assert!(db.search(query, 1).is_ok(), "{query}");
+    }
+
+    let candidates = db.search("population côte d'ivoire", 1).unwrap();
+    assert_eq!(description(&candidates[0]), "Population of Côte d'Ivoire");
+}
+
+#[test]
+fn test_entity_attribute() {
+    let mut facts = Facts::new();
+    facts.load_toml(ENTITIES).unwrap();
+    let db = Db::in_memory_with(&facts).unwrap();
+
+    let mass = db.get("Mars", "mass").unwrap().unwrap();
+    assert_eq!(&*mass.description, "Mass of Mars");
+    assert!(db.get("mars", "colour").unwrap().is_none());
+    assert!(db.entity("vulcan").unwrap().is_empty());
+
+    let population = db.get("united_states_of_america", "population").unwrap();
+    assert!(population.is_some());
+
+    assert_eq!(
+        query!("mars.mass", facts = &facts),
+        query!("mass of mars", facts = &facts)
+    );
+    assert_eq!(
+        query!("radius(mars)", facts = &facts),
+        query!("mars.radius", facts = &facts)
+    );
+    assert_eq!(
+        query!("united_states_of_america.population", facts = &facts),
+        query!("population of united states of america", facts = &facts)
+    );
+
+    let error = query_err!("mars.colour", facts = &facts);
+    assert_eq!(
+        error.to_string(),
+        "`mars` has no attribute `colour`, it has: mass, radius"
+    );
+
+    let error = query_err!("vulcan.mass", facts = &facts);
+    assert_eq!(error.to_string(), "no entity named `vulcan`");
+
+    // NB: attributes of entities are resolved without searching.
+    let mut descriptions = Vec::new();
+    query!(
+        "mass of mars",
+        facts = &facts,
+        options = Options::default().candidates(),
+        descriptions = &mut descriptions
+    );
+    assert!(descriptions.is_empty());
+}
+
+#[test]
+#[ignore = "db/astronomics.bin.gz has to be regenerated with `cargo run -p assets` to have entities"]
+fn test_embedded_entities() {
+    assert_eq!(query!("mars.mass"), query!("mass of mars"));
+    assert_eq!(query!("moon.radius"), query!("radius of the moon"));
+    assert_eq!(query!("population(finland)"), query!("finland.population"));
+}
+
+#[test]
+#[ignore = "db/populations.bin.gz has to be regenerated with `cargo run -p assets` to have series"]
+fn test_embedded_series() {
+    let db = Db::in_memory().unwrap();
+    let candidates = db.lookup("population niger").unwrap();
+    assert_eq!(&*candidates[0].constant.description, "Population of Niger");
+
+    let latest = query!("population finland");
+    let past = query!("population finland in 1990");
+    assert!(past.value < latest.value);
+    assert_eq!(query!("population(finland, 1990)"), past);
+
+    assert!(query!("population(world, 2000)").value < query!("population world").value);
+}
@@ -5,7 +5,11 @@ fn test_tonne() {
 
 #[test]
 fn test_dalton() {
-    assert_query!("1Da to kg", 332107813321 / 200000000000, kg);
+    assert_query!(
+        "1Da to kg",
+        166053906660 / 100000000000000000000000000000000000000,
+        kg
+    );
 }
 
 #[test]
@@ -0,0 +1,53 @@
+use anything::Rational;
+
+fn parts(query: &str) -> Vec<(Rational, String)> {
+    query!(query)
+        .parts()
+        .unwrap()
+        .into_iter()
+        .map(|(value, unit)| (value, unit.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_mixed_input() {
+    assert_query!("5ft 11in", 71, in);
+    assert_query!("5 ft 11 in", 71, in);
+    assert_query!("2 hr 13 min 4 s", 7984, s);
+    assert_query!("1 hr 30 min to min", 90, min);
+
+    let n = query!("-5ft 11in");
+    assert_eq!(n.value, Rational::new(-71, 1));
+    assert_eq!(n.unit, unit!("in"));
+}
+
+#[test]
+fn test_mixed_output() {
+    assert_eq!(
+        parts("71 in to ft+in"),
+        [(ratio!(5), "ft".into()), (ratio!(11), "in".into())]
+    );
+
+    assert_eq!(
+        parts("8000 s to hr+min+s"),
+        [
+            (ratio!(2), "hr".into()),
+            (ratio!(13), "min".into()),
+            (ratio!(20), "s".into())
+        ]
+    );
+
+    assert_eq!(
+        parts("330 s to hms"),
+        [(ratio!(5), "min".into()), (ratio!(30), "s".into())]
+    );
+
+    assert_eq!(
+        parts("1 m to ft+in"),
+        [(ratio!(3), "ft".into()), (ratio!(428 / 127), "in".into())]
+    );
+
+    let n = query!("5ft 11in to ft+in");
+    assert_eq!(n.value, ratio!(71));
+    assert_eq!(n.unit, unit!("in"));
+}
@@ -0,0 +1,125 @@
+use anything::rational::DisplaySpec;
+
+#[test]
+fn test_uncertainty_parse() {
+    let n = query!("9.81 ± 0.02 m/s^2");
+    assert_eq!(n.value, ratio!(981 / 100));
+    assert_eq!(n.uncertainty, Some(ratio!(2 / 100)));
+    assert_eq!(n.unit, unit!("m/s^2"));
+    assert_eq!(
+        n.display_uncertainty(&Default::default()).as_deref(),
+        Some("9.810 ± 0.020")
+    );
+
+    let n = query!("10 ± 5% m");
+    assert_eq!(n.value, ratio!(10));
+    assert_eq!(n.uncertainty, Some(ratio!(1 / 2)));
+    assert_eq!(n.unit, unit!("m"));
+
+    let n = query!("5 ± 0.1 m to cm");
+    assert_eq!(n.value, ratio!(500));
+    assert_eq!(n.uncertainty, Some(ratio!(10)));
+    assert_eq!(n.unit, unit!("cm"));
+}
+
+#[test]
+fn test_uncertainty_propagation() {
+    let n = query!("(10 ± 3) + (20 ± 4)");
+    assert_eq!(n.value, ratio!(30));
+    assert_eq!(n.uncertainty, Some(ratio!(5)));
+
+    let n = query!("(20 ± 4) - (10 ± 3)");
+    assert_eq!(n.value, ratio!(10));
+    assert_eq!(n.uncertainty, Some(ratio!(5)));
+
+    let n = query!("(10 ± 0.3) * (20 ± 0.8)");
+    assert_eq!(n.value, ratio!(200));
+    assert_eq!(n.uncertainty, Some(ratio!(10)));
+
+    let n = query!("(20 ± 0.8) / (10 ± 0.3)");
+    assert_eq!(n.value, ratio!(2));
+    assert_eq!(n.uncertainty, Some(ratio!(1 / 10)));
+
+    let n = query!("(2 ± 0.1)^3");
+    assert_eq!(n.value, ratio!(8));
+    assert_eq!(n.uncertainty, Some(ratio!(6 / 5)));
+
+    let n = query!("(2 ± 0.1 m) * 3");
+    assert_eq!(n.value, ratio!(6));
+    assert_eq!(n.uncertainty, Some(ratio!(3 / 10)));
+    assert_eq!(n.unit, unit!("m"));
+
+    let n = query!("2 m + 3 m");
+    assert_eq!(n.uncertainty, None);
+}
+
+#[test]
+fn test_constant_uncertainty() {
+    let n = query!("G");
+    assert!(!n.exact);
+    assert!(n.uncertainty.is_some());
+    assert_eq!(
+        n.display_uncertainty(&Default::default()).as_deref(),
+        Some("(6.67430 ± 0.00015)e-11")
+    );
+
+    let mut spec = DisplaySpec::default();
+    spec.exponent_limit = 3;
+
+    let n = query!("1234 ± 5");
+    assert_eq!(
+        n.display_uncertainty(&spec).as_deref(),
+        Some("(1.2340 ± 0.0050)e3")
+    );
+    assert_eq!(
+        n.display_uncertainty(&Default::default()).as_deref(),
+        Some("1234.0 ± 5.0")
+    );
+}
+
+#[test]
+fn test_function_uncertainty() {
+    let n = query!("abs(0 - (2 ± 0.1))");
+    assert_eq!(n.value, ratio!(2));
+    assert_eq!(n.uncertainty, Some(ratio!(1 / 10)));
+
+    let n = query!("hypot(3 ± 0.1 m, 4 m)");
+    assert_eq!(n.value, ratio!(5));
+    assert_eq!(
+        n.display_uncertainty(&Default::default()).as_deref(),
+        Some("5.000 ± 0.061")
+    );
+
+    let n = query!("min(300 cm, 3.5 ± 0.1 m)");
+    assert_eq!(n.value, ratio!(300));
+    assert_eq!(n.uncertainty, None);
+
+    let n = query!("sqrt(4 ± 0.4)");
+    assert_eq!(n.uncertainty, Some(ratio!(1 / 10)));
+
+    let n = query!("sin(1 ± 0.01)");
+    assert_eq!(
+        n.display_uncertainty(&Default::default()).as_deref(),
+        Some("0.8415 ± 0.0054")
+    );
+
+    // NB: integer functions can't be evaluated with a raised argument.
+    let n = query!("gcd(12 ± 0.5, 18)");
+    assert_eq!(n.value, ratio!(6));
+    assert!(!n.exact);
+}
+
+#[test]
+fn test_user_function_uncertainty() {
+    let n = query!("f(x: m) = x * 2; f(1 ± 0.1 m)");
+    assert_eq!(n.value, ratio!(2));
+    assert_eq!(n.unit, unit!("m"));
+    assert_eq!(n.uncertainty, Some(ratio!(2 / 10)));
+
+    let n = query!("f(x: cm) = x; f(1 ± 0.1 m)");
+    assert_eq!(n.value, ratio!(100));
+    assert_eq!(n.uncertainty, Some(ratio!(10)));
+
+    let n = query!("f(t: s) = t + 1 s; f(2026-10-18)");
+    assert!(n.is_instant());
+}
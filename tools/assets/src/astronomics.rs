@@ -101,25 +101,40 @@ async fn download_planets(db: &mut Db) -> Result<()> {
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "orbit".into(), "distance".into()],
+            entity: Some(p.name.as_str().into()),
+            attribute: Some("orbital distance".into()),
             description: format!("Orbital distance of {}", p.name).into(),
             unit: str::parse("au")?,
             value: p.distance_from_sun / &mkm_in_au,
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
 
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "orbital".into(), "period".into()],
+            entity: Some(p.name.as_str().into()),
+            attribute: Some("orbital period".into()),
             description: format!("Orbital period of {}", p.name).into(),
             unit: str::parse("yr")?,
             value: p.orbital_period / &days_in_year,
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
 
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "mass".into()],
+            entity: Some(p.name.as_str().into()),
+            attribute: Some("mass".into()),
             description: format!("Mass of {}", p.name).into(),
             unit: str::parse("kg")?,
             value: p.mass * &mass_ratio,
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
 
         db.constants.push(Constant {
@@ -130,25 +145,40 @@ async fn download_planets(db: &mut Db) -> Result<()> {
                 "day".into(),
                 "length".into(),
             ],
+            entity: Some(p.name.as_str().into()),
+            attribute: Some("solar day".into()),
             description: format!("Length of a solar day on {}", p.name).into(),
             unit: str::parse("dy")?,
             value: p.length_of_day / &hours_in_day,
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
 
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "diameter".into()],
+            entity: Some(p.name.as_str().into()),
+            attribute: Some("diameter".into()),
             description: format!("Diameter of {}", p.name).into(),
             unit: str::parse("km")?,
             value: p.diameter.clone(),
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
 
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "radius".into()],
+            entity: Some(p.name.as_str().into()),
+            attribute: Some("radius".into()),
             description: format!("Radius of {}", p.name).into(),
             unit: str::parse("km")?,
             value: &p.diameter / &two,
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
     }
 
@@ -195,25 +225,40 @@ async fn download_satellites(db: &mut Db) -> Result<()> {
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "mass".into()],
+            entity: Some(s.name.as_str().into()),
+            attribute: Some("mass".into()),
             description: format!("Mass of the satellite {}", s.name).into(),
             unit: str::parse("kg")?,
             value: s.gm * &kmc_to_mc / &big_g,
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
 
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "radius".into()],
+            entity: Some(s.name.as_str().into()),
+            attribute: Some("radius".into()),
             description: format!("Radius of the satellite {}", s.name).into(),
             unit: str::parse("km")?,
             value: s.radius.clone(),
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
 
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: vec![search_name.clone(), "diameter".into()],
+            entity: Some(s.name.as_str().into()),
+            attribute: Some("diameter".into()),
             description: format!("Diameter of the satellite {}", s.name).into(),
             unit: str::parse("km")?,
             value: &s.radius * &two,
+            exact: false,
+            uncertainty: None,
+            series: Default::default(),
         });
     }
 
@@ -0,0 +1,25 @@
+use anyhow::{anyhow, Result};
+use anything::Severity;
+
+/// Check the database embedded in the `anything` crate, printing every
+/// problem found. Fails if any of them is an error.
+pub fn check() -> Result<()> {
+    let problems = anything::check()?;
+    let mut errors = 0;
+
+    for problem in &problems {
+        println!("{problem}");
+
+        if problem.severity == Severity::Error {
+            errors += 1;
+        }
+    }
+
+    println!("{} problems, of which {errors} are errors", problems.len());
+
+    if errors > 0 {
+        return Err(anyhow!("the database has {errors} errors"));
+    }
+
+    Ok(())
+}
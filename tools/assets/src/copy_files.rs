@@ -1,27 +1,9 @@
 use crate::db::Db;
-use anyhow::{anyhow, Result};
-use anything::{Compound, Constant, Rational};
-use serde::de;
-use serde::Deserialize;
-use std::{borrow::Cow, path::PathBuf};
+use anyhow::{anyhow, Context, Result};
+use anything::Facts;
+use std::path::PathBuf;
 use tokio::fs;
 
-#[derive(Debug, Deserialize)]
-struct InnerConstant {
-    tokens: Vec<Box<str>>,
-    description: Box<str>,
-    #[serde(deserialize_with = "deserialize_value")]
-    value: Rational,
-    #[serde(default, deserialize_with = "deserialize_unit")]
-    unit: Option<Compound>,
-}
-
-#[derive(Debug, Deserialize)]
-struct InnerDb {
-    #[serde(default)]
-    constants: Vec<InnerConstant>,
-}
-
 /// Copy files.
 pub async fn copy_files(db: &mut Db) -> Result<()> {
     let dir = match std::env::var_os("CARGO_MANIFEST_DIR") {
@@ -35,40 +17,15 @@ pub async fn copy_files(db: &mut Db) -> Result<()> {
     for e in std::fs::read_dir(&db_path)? {
         let e = e?;
         let content = fs::read_to_string(e.path()).await?;
-        let inner_db: InnerDb = toml::from_str(&content)?;
-
-        for c in inner_db.constants {
-            db.constants.push(Constant {
-                source: None,
-                tokens: c.tokens,
-                description: c.description,
-                value: c.value,
-                unit: c.unit.unwrap_or_default(),
-            });
-        }
-    }
 
-    Ok(())
-}
+        let mut facts = Facts::new();
 
-fn deserialize_value<'de, D>(deserializer: D) -> Result<Rational, D::Error>
-where
-    D: de::Deserializer<'de>,
-{
-    let s = Cow::<'de, str>::deserialize(deserializer)?;
-    str::parse::<Rational>(s.as_ref()).map_err(<D::Error as de::Error>::custom)
-}
+        facts
+            .load_toml(&content)
+            .with_context(|| anyhow!("{}", e.path().display()))?;
 
-fn deserialize_unit<'de, D>(deserializer: D) -> Result<Option<Compound>, D::Error>
-where
-    D: de::Deserializer<'de>,
-{
-    let s = match Option::<Cow<'de, str>>::deserialize(deserializer)? {
-        Some(s) => s,
-        None => return Ok(None),
-    };
+        db.constants.extend(facts.constants().iter().cloned());
+    }
 
-    Ok(Some(
-        str::parse::<Compound>(s.as_ref()).map_err(<D::Error as de::Error>::custom)?,
-    ))
+    Ok(())
 }
@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context, Result};
+use anything::{Day, Rates, Rational, Source};
+
+use crate::cache;
+use crate::db::Sources;
+
+pub const SOURCE: u64 = 0x4f3b0e8a61c2d975;
+
+const URL: &str = "https://www.ecb.europa.eu/stats/eurofxref/eurofxref-hist.xml";
+
+/// Download euro foreign exchange reference rates.
+pub async fn download(rates: &mut Rates, sources: &mut Sources) -> Result<()> {
+    sources.sources.push(source());
+
+    let bytes = cache::get("currencies", URL).await?;
+    let xml = std::str::from_utf8(&bytes)?;
+
+    let mut day: Option<Day> = None;
+
+    // NB: The document is a flat list of `<Cube time='..'>` elements, each
+    // containing one `<Cube currency='..' rate='..'/>` element per currency.
+    for tag in xml.split('<').filter(|tag| tag.starts_with("Cube ")) {
+        if let Some(date) = attribute(tag, "time") {
+            if let Some(day) = day.take() {
+                rates.insert(day)?;
+            }
+
+            day = Some(Day {
+                date: date.into(),
+                source: Some(SOURCE),
+                rates: BTreeMap::new(),
+            });
+
+            continue;
+        }
+
+        let (Some(code), Some(rate)) = (attribute(tag, "currency"), attribute(tag, "rate")) else {
+            continue;
+        };
+
+        let day = day
+            .as_mut()
+            .ok_or_else(|| anyhow!("{code}: rate outside of a day"))?;
+
+        let rate = str::parse::<Rational>(rate).with_context(|| anyhow!("{code}: bad rate"))?;
+        day.rates.insert(code.into(), rate);
+    }
+
+    if let Some(day) = day.take() {
+        rates.insert(day)?;
+    }
+
+    Ok(())
+}
+
+/// The source of exchange rates.
+pub fn source() -> Source {
+    Source {
+        id: SOURCE,
+        description: "Euro foreign exchange reference rates from the ECB".into(),
+        url: Some("https://www.ecb.europa.eu/stats/policy_and_exchange_rates/euro_reference_exchange_rates/html/index.en.html".into()),
+    }
+}
+
+/// Get the value of the attribute with the given name in an XML tag.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!("{name}="))? + name.len() + 1;
+    let rest = &tag[start..];
+    let quote = rest.chars().next().filter(|c| matches!(c, '\'' | '"'))?;
+    let rest = &rest[1..];
+    let end = rest.find(quote)?;
+    Some(&rest[..end])
+}
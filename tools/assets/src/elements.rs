@@ -0,0 +1,112 @@
+use anyhow::{anyhow, Context, Result};
+use anything::{Constant, Rational, Source};
+use serde::Deserialize;
+
+use crate::cache;
+use crate::db::{Db, Sources};
+
+const SOURCE: u64 = 0x5c4e1d7f0b9a3e62;
+
+const URL: &str = "https://pubchem.ncbi.nlm.nih.gov/rest/pug/periodictable/JSON";
+
+#[derive(Debug, Deserialize)]
+struct Doc {
+    #[serde(rename = "Table")]
+    table: Table,
+}
+
+#[derive(Debug, Deserialize)]
+struct Table {
+    #[serde(rename = "Columns")]
+    columns: Columns,
+    #[serde(rename = "Row")]
+    rows: Vec<Row>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Columns {
+    #[serde(rename = "Column")]
+    column: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Row {
+    #[serde(rename = "Cell")]
+    cell: Vec<String>,
+}
+
+/// Download and format the atomic mass, density and melting point of each
+/// element.
+pub async fn download(db: &mut Db, sources: &mut Sources) -> Result<()> {
+    sources.sources.push(Source {
+        id: SOURCE,
+        description: "Periodic table of elements from PubChem".into(),
+        url: Some("https://pubchem.ncbi.nlm.nih.gov/periodic-table/".into()),
+    });
+
+    println!("Downloading elements");
+    let bytes = cache::get("elements", URL).await?;
+
+    println!("Deserializing elements");
+    let doc: Doc = serde_json::from_slice(&bytes)?;
+
+    let column = |name: &str| {
+        doc.table
+            .columns
+            .column
+            .iter()
+            .position(|c| c == name)
+            .ok_or_else(|| anyhow!("missing column `{name}`"))
+    };
+
+    let names = column("Name")?;
+
+    // NB: densities are in `g/cm^3` and melting points in kelvin, also for
+    // elements which are gases at room temperature.
+    let attributes = [
+        (column("AtomicMass")?, "atomic mass", "Da"),
+        (column("Density")?, "density", "g/cm^3"),
+        (column("MeltingPoint")?, "melting point", "K"),
+    ];
+
+    for row in &doc.table.rows {
+        let Some(name) = row.cell.get(names) else {
+            continue;
+        };
+
+        let search_name = Box::<str>::from(name.to_lowercase());
+
+        for &(n, attribute, unit) in &attributes {
+            // NB: the mass number of the most stable isotope of some
+            // radioactive elements is in brackets, like `[209]`.
+            let value = match row.cell.get(n).map(|v| v.trim_matches(['[', ']'])) {
+                Some(value) if !value.is_empty() => value,
+                _ => continue,
+            };
+
+            let value = str::parse::<Rational>(value)
+                .with_context(|| anyhow!("{name}: bad {attribute} `{value}`"))?;
+
+            let mut tokens = vec![search_name.clone()];
+            tokens.extend(attribute.split(' ').map(Box::from));
+
+            let mut description = attribute.to_owned();
+            description[..1].make_ascii_uppercase();
+
+            db.constants.push(Constant {
+                source: Some(SOURCE),
+                tokens,
+                entity: Some(name.as_str().into()),
+                attribute: Some(attribute.into()),
+                description: format!("{description} of {name}").into(),
+                unit: str::parse(unit)?,
+                value,
+                exact: false,
+                uncertainty: None,
+                series: Default::default(),
+            });
+        }
+    }
+
+    Ok(())
+}
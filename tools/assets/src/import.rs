@@ -0,0 +1,47 @@
+use crate::db::{Db, Sources};
+use anyhow::{anyhow, Result};
+use anything::{Facts, Import};
+use std::fs;
+use std::path::PathBuf;
+
+/// The directory mapping files are read from.
+const IMPORT: &str = "import";
+
+/// Import facts from local CSV, JSON or XLSX files, as described by every
+/// mapping file in the `import` directory.
+///
+/// Mappings should give their source an `id`, so that it doesn't depend on
+/// where the repository is checked out.
+pub fn import(db: &mut Db, sources: &mut Sources) -> Result<()> {
+    let dir = match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => PathBuf::from(dir).join(IMPORT),
+        None => return Err(anyhow!("missing `CARGO_MANIFEST_DIR`")),
+    };
+
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    let mut paths = Vec::new();
+
+    for e in fs::read_dir(&dir)? {
+        let path = e?.path();
+
+        if path.extension().is_some_and(|e| e == "toml") {
+            paths.push(path);
+        }
+    }
+
+    paths.sort();
+
+    let mut facts = Facts::new();
+
+    for path in paths {
+        println!("Importing {}", path.display());
+        facts.load_import(&Import::open(&path)?)?;
+    }
+
+    db.constants.extend(facts.constants().iter().cloned());
+    sources.sources.extend(facts.sources().iter().cloned());
+    Ok(())
+}
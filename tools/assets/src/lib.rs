@@ -1,7 +1,11 @@
 pub mod analyzer;
 pub mod astronomics;
 mod cache;
+pub mod check;
 pub mod copy_files;
+pub mod currencies;
 pub mod db;
+pub mod elements;
 mod helpers;
+pub mod import;
 pub mod populations;
@@ -2,6 +2,12 @@ use assets::db;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    match std::env::args().nth(1).as_deref() {
+        Some("check") => return assets::check::check(),
+        Some(command) => return Err(anyhow::anyhow!("unknown command `{command}`")),
+        None => {}
+    }
+
     let analyzer = assets::analyzer::Analyzer::default();
     let mut sources = db::Sources::default();
 
@@ -17,12 +23,33 @@ async fn main() -> anyhow::Result<()> {
         db::to_path("db/populations.bin.gz", &db)?;
     }
 
+    {
+        let mut db = db::Db::default();
+        assets::elements::download(&mut db, &mut sources).await?;
+        db::to_path("db/elements.bin.gz", &db)?;
+    }
+
+    {
+        let mut rates = anything::Rates::new();
+        assets::currencies::download(&mut rates, &mut sources).await?;
+        db::to_path("db/currencies.bin.gz", &rates)?;
+    }
+
     {
         let mut db = db::Db::default();
         assets::copy_files::copy_files(&mut db).await?;
         db::to_path("db/files.bin.gz", &db)?;
     }
 
+    {
+        let mut db = db::Db::default();
+        assets::import::import(&mut db, &mut sources)?;
+
+        if !db.constants.is_empty() {
+            db::to_path("db/imports.bin.gz", &db)?;
+        }
+    }
+
     db::to_path("db/sources.bin.gz", &sources)?;
     Ok(())
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::Cursor;
 
 use anyhow::{anyhow, Context, Result};
@@ -31,38 +32,52 @@ pub async fn download(analyzer: &Analyzer, db: &mut Db, sources: &mut Sources) -
     let first = it.next().context("couldn't find first row")?;
 
     let mut years = Vec::new();
-    let mut last_year = None;
 
     for (n, col) in first.iter().enumerate().skip(7) {
         if let Some(year) = col.as_string() {
-            let year = year.parse::<u32>()?;
+            let year = year.parse::<i32>()?;
             years.push((year, n));
-            last_year = Some((year, n));
         }
     }
 
-    let (last_year_number, last_year) = match last_year {
-        Some(last_year) => last_year,
-        None => return Err(anyhow!("missing last year")),
-    };
+    if years.is_empty() {
+        return Err(anyhow!("missing years"));
+    }
 
     let thousand = Rational::new(1000u32, 1u32);
 
     for row in it {
-        let region = if let [Data::Float(..), Data::String(..), Data::String(region), ..] = row {
-            region
-        } else {
+        let [Data::Float(..), Data::String(..), Data::String(region), _, _, Data::String(kind), ..] =
+            row
+        else {
             continue;
         };
 
-        let population = match row.get(last_year) {
-            Some(Data::Float(population)) => {
-                Rational::from_f64(*population).unwrap_or_else(|| Rational::new(1, 1)) * &thousand
-            }
-            Some(Data::Int(population)) => Rational::new(*population, 1u32) * &thousand,
-            _ => {
-                continue;
-            }
+        // NB: the SDG regions regroup the geographic regions, and some of them
+        // are listed under both, like `EUROPE` and `Europe`.
+        if kind.starts_with("SDG") {
+            continue;
+        }
+
+        let mut series = BTreeMap::new();
+
+        for &(year, n) in &years {
+            let population = match row.get(n) {
+                Some(Data::Float(population)) => {
+                    Rational::from_f64(*population).unwrap_or_else(|| Rational::new(1, 1))
+                        * &thousand
+                }
+                Some(Data::Int(population)) => Rational::new(*population, 1u32) * &thousand,
+                _ => {
+                    continue;
+                }
+            };
+
+            series.insert(year, population);
+        }
+
+        let Some((_, population)) = series.last_key_value() else {
+            continue;
         };
 
         let mut names = Vec::new();
@@ -72,9 +87,14 @@ pub async fn download(analyzer: &Analyzer, db: &mut Db, sources: &mut Sources) -
         db.constants.push(Constant {
             source: Some(SOURCE),
             tokens: names,
-            description: format!("Population of {region} in {last_year_number}").into(),
+            entity: Some(region.as_str().into()),
+            attribute: Some("population".into()),
+            description: format!("Population of {region}").into(),
             unit: Default::default(),
-            value: population,
+            value: population.clone(),
+            exact: false,
+            uncertainty: None,
+            series,
         });
     }
 